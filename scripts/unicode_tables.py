@@ -0,0 +1,211 @@
+#!/usr/bin/env python3
+"""Generates src/core/parse/identifier/tables.rs and src/interner/normalize/tables.rs
+from Python's unicodedata.
+
+Run from the repository root: python3 scripts/unicode_tables.py
+"""
+
+import sys
+import unicodedata
+
+OUTPUT = "src/core/parse/identifier/tables.rs"
+FOLD_OUTPUT = "src/interner/normalize/tables.rs"
+
+MAX = sys.maxunicode + 1
+
+
+def ranges(predicate):
+    result = []
+    start = None
+
+    for code in range(MAX):
+        if predicate(code):
+            if start is None:
+                start = code
+        elif start is not None:
+            result.append((start, code - 1))
+            start = None
+
+    if start is not None:
+        result.append((start, MAX - 1))
+
+    return result
+
+
+def is_char(code):
+    return not 0xD800 <= code <= 0xDFFF
+
+
+def xid_start(code):
+    c = chr(code)
+    return is_char(code) and c != "_" and c.isidentifier()
+
+
+def xid_continue(code):
+    return is_char(code) and ("a" + chr(code)).isidentifier()
+
+
+def nfc_no(code):
+    c = chr(code)
+    return is_char(code) and unicodedata.normalize("NFC", c) != c
+
+
+def compositions():
+    pairs = []
+
+    for code in range(MAX):
+        if not is_char(code):
+            continue
+
+        decomposition = unicodedata.decomposition(chr(code))
+
+        if not decomposition or decomposition.startswith("<"):
+            continue
+
+        parts = [int(part, 16) for part in decomposition.split()]
+
+        # Only primary composites recompose
+        if len(parts) == 2 and unicodedata.normalize("NFC", chr(parts[0]) + chr(parts[1])) == chr(code):
+            pairs.append((parts[0], parts[1]))
+
+    return sorted(pairs)
+
+
+def decompositions():
+    entries = []
+
+    for code in range(MAX):
+        if not is_char(code):
+            continue
+
+        decomposition = unicodedata.decomposition(chr(code))
+
+        # Hangul syllables decompose algorithmically and have no entry
+        if decomposition and not decomposition.startswith("<"):
+            entries.append((code, unicodedata.normalize("NFD", chr(code))))
+
+    return entries
+
+
+def nfc_maybe(pairs):
+    seconds = {second for _, second in pairs}
+    # Hangul vowel and trailing jamo compose with preceding syllables
+    seconds.update(range(0x1161, 0x1176))
+    seconds.update(range(0x11A8, 0x11C3))
+
+    return ranges(lambda code: code in seconds)
+
+
+def class_table(name, doc, entries):
+    lines = [f"/// {doc}", f"pub(super) const {name}: &[(u32, u32, u8)] = &["]
+    lines += [f"    (0x{a:04X}, 0x{b:04X}, {ccc})," for a, b, ccc in entries]
+    lines.append("];")
+    return "\n".join(lines)
+
+
+def case_folding():
+    entries = []
+
+    for code in range(MAX):
+        if not is_char(code):
+            continue
+
+        c = chr(code)
+        folded = c.casefold()
+
+        if folded != c:
+            entries.append((code, folded))
+
+    return entries
+
+
+def rust_str(string):
+    return '"' + "".join(f"\\u{{{ord(c):04X}}}" for c in string) + '"'
+
+
+def decomposition_table(name, doc, entries):
+    lines = [f"/// {doc}", f"pub(super) const {name}: &[(char, &str)] = &["]
+    lines += [f"    ('\\u{{{code:04X}}}', {rust_str(string)})," for code, string in entries]
+    lines.append("];")
+    return "\n".join(lines)
+
+
+def composition_table(name, doc, pairs):
+    lines = [f"/// {doc}", f"pub(super) const {name}: &[(char, char, char)] = &["]
+
+    for first, second in pairs:
+        composed = ord(unicodedata.normalize("NFC", chr(first) + chr(second)))
+        lines.append(f"    ('\\u{{{first:04X}}}', '\\u{{{second:04X}}}', '\\u{{{composed:04X}}}'),")
+
+    lines.append("];")
+    return "\n".join(lines)
+
+
+def combining_classes():
+    result = []
+
+    for code in range(MAX):
+        ccc = unicodedata.combining(chr(code)) if is_char(code) else 0
+
+        if ccc == 0:
+            continue
+
+        if result and result[-1][1] == code - 1 and result[-1][2] == ccc:
+            result[-1] = (result[-1][0], code, ccc)
+        else:
+            result.append((code, code, ccc))
+
+    return result
+
+
+def table(name, doc, entries):
+    lines = [f"/// {doc}", f"pub(super) const {name}: &[(u32, u32)] = &["]
+    lines += [f"    (0x{a:04X}, 0x{b:04X})," for a, b in entries]
+    lines.append("];")
+    return "\n".join(lines)
+
+
+def main():
+    pairs = compositions()
+
+    sections = [
+        f"// Generated by scripts/unicode_tables.py from Unicode {unicodedata.unidata_version}, do not edit",
+        table("XID_START", "Ranges of XID_Start", ranges(xid_start)),
+        table("XID_CONTINUE", "Ranges of XID_Continue", ranges(xid_continue)),
+        table("NFC_NO", "Ranges with NFC_Quick_Check=No", ranges(nfc_no)),
+        table("NFC_MAYBE", "Ranges with NFC_Quick_Check=Maybe", nfc_maybe(pairs)),
+        class_table(
+            "COMBINING_CLASSES",
+            "Ranges with a non-zero Canonical_Combining_Class and their class",
+            combining_classes(),
+        ),
+        decomposition_table(
+            "DECOMPOSITIONS",
+            "Chars sorted by code point with their full canonical decomposition, excluding Hangul",
+            decompositions(),
+        ),
+        composition_table(
+            "COMPOSITIONS",
+            "Sorted pairs of chars that compose canonically and their composite, excluding Hangul",
+            pairs,
+        ),
+    ]
+
+    with open(OUTPUT, "w") as file:
+        file.write("\n\n".join(sections) + "\n")
+
+    lines = [
+        f"// Generated by scripts/unicode_tables.py from Unicode {unicodedata.unidata_version}, do not edit",
+        "",
+        "/// Chars sorted by code point with their full case folding, from CaseFolding.txt (C + F)",
+        "pub(super) const CASE_FOLDING: &[(char, &str)] = &[",
+    ]
+    lines += [f"    ('\\u{{{code:04X}}}', {rust_str(folded)})," for code, folded in case_folding()]
+    lines.append("];")
+
+    with open(FOLD_OUTPUT, "w") as file:
+        file.write("\n".join(lines) + "\n")
+
+
+if __name__ == "__main__":
+    main()
@@ -1,7 +1,40 @@
+use token_precedence::span::{Span, Spanned};
+
 use crate::{
-    input::{Entry, Input, InputExt},
-    parse::{IsParse, ParseError},
+    input::{self, Entry, Input, InputExt, ReadError},
+    interner::{Id, Interner},
+    parse::{IsParse, ParseError, RichError},
+};
+
+mod chars;
+mod identifier;
+mod literal_set;
+mod number;
+mod string;
+mod tag;
+pub use chars::{AnyChar, CharSet, ExpectedChar, NoneOf, OneOf, Satisfy};
+pub use identifier::{
+    Identifier, IdentifierError, IdentifierErrorKind, is_nfc, is_xid_continue, is_xid_start,
+};
+pub use literal_set::{ExpectedLiteral, LiteralSet};
+pub use number::{
+    BigInt, Float, FloatLiteral, Integer, IntegerLiteral, NumberError, NumberErrorKind,
 };
+pub use string::{QuotedString, StringError, StringErrorKind};
+pub use tag::{ExpectedTag, KeywordTag, Tag, TagNoCase};
+
+/// Reads at least `n` bytes, or everything left if the input ends sooner
+#[inline(always)]
+pub(crate) fn read_ahead<I: ?Sized + Input>(input: &mut I, n: usize) -> input::Result<&str> {
+    // Up to 3 bytes of an incomplete char may be held back by `buffer_at_least`
+    match input.buffer_at_least(n + 3) {
+        Ok(()) => {}
+        Err(ReadError::EOF) if !input.is_eof() => {}
+        Err(err) => return Err(err),
+    }
+
+    Ok(input.read())
+}
 
 #[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
 pub struct SplitUpTo<F: Fn(char) -> bool> {
@@ -29,3 +62,135 @@ impl<'a, F: Fn(char) -> bool> IsParse<'a> for SplitUpTo<F> {
             .map(Entry::unsize)
     }
 }
+
+/// Reads the longest run of chars matching `func` and interns it,
+/// without copying the text out of the input buffer first
+pub struct InternIdent<'i, F: Fn(char) -> bool> {
+    interner: &'i mut Interner,
+    func: F,
+}
+
+impl<'i, F: Fn(char) -> bool> InternIdent<'i, F> {
+    #[inline(always)]
+    pub const fn new(interner: &'i mut Interner, func: F) -> Self {
+        Self { interner, func }
+    }
+
+    #[inline(always)]
+    pub fn interner(&self) -> &Interner {
+        self.interner
+    }
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub struct ExpectedIdent {
+    pub span: Span,
+}
+
+impl From<ExpectedIdent> for RichError {
+    #[inline(always)]
+    fn from(value: ExpectedIdent) -> Self {
+        RichError::new(value.span).expecting("identifier")
+    }
+}
+
+impl<'a, 'i, F: Fn(char) -> bool> IsParse<'a> for InternIdent<'i, F> {
+    type Output = Spanned<Id>;
+    type Error = ExpectedIdent;
+
+    #[inline(always)]
+    fn __parse<I: ?Sized + Input>(
+        mut self,
+        input: &'a mut I,
+    ) -> Result<Self::Output, ParseError<Self::Error>> {
+        (&mut self).__parse(input)
+    }
+}
+
+impl<'a, 'i, F: Fn(char) -> bool> IsParse<'a> for &mut InternIdent<'i, F> {
+    type Output = Spanned<Id>;
+    type Error = ExpectedIdent;
+
+    fn __parse<I: ?Sized + Input>(
+        self,
+        input: &'a mut I,
+    ) -> Result<Self::Output, ParseError<Self::Error>> {
+        let entry = input.read_until_entry(8, |c| !(self.func)(c))?;
+
+        if entry.get().is_empty() {
+            return Err(ParseError::Error(ExpectedIdent { span: entry.span() }));
+        }
+
+        Ok(self.interner.insert_entry(entry))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::{
+        core::trim::TrimWhitespace,
+        input::{ReaderInput, StrView},
+        parse::{Parse, ParseMutIter},
+    };
+
+    use super::*;
+
+    fn is_ident(c: char) -> bool {
+        c.is_alphanumeric() || c == '_'
+    }
+
+    #[test]
+    fn intern_idents() {
+        let mut interner = Interner::new();
+        let mut input = StrView::new("foo bar foo");
+
+        let idents = ParseMutIter::new(
+            &mut input,
+            TrimWhitespace,
+            InternIdent::new(&mut interner, is_ident),
+        )
+        .unwrap()
+        .map(Result::unwrap)
+        .collect::<Vec<_>>();
+
+        let foo = interner.get("foo").unwrap();
+        let bar = interner.get("bar").unwrap();
+
+        assert_eq!(
+            idents,
+            [
+                Span::new(0, 3).over(foo),
+                Span::new(4, 7).over(bar),
+                Span::new(8, 11).over(foo)
+            ]
+        );
+        assert_eq!(interner.len(), 2);
+    }
+
+    #[test]
+    fn intern_long_ident() {
+        let mut interner = Interner::new();
+        let mut input = ReaderInput::<_, 64>::new("a_long_identifier_name+".as_bytes());
+
+        let ident = InternIdent::new(&mut interner, is_ident)
+            .parse(&mut input)
+            .unwrap();
+
+        assert_eq!(ident.span, Span::new(0, 22));
+        assert_eq!(interner.resolve(ident.inner), "a_long_identifier_name");
+        assert_eq!(input.read(), "+");
+    }
+
+    #[test]
+    fn expected_ident() {
+        let mut interner = Interner::new();
+        let mut input = StrView::new("+");
+
+        assert_eq!(
+            InternIdent::new(&mut interner, is_ident).parse(&mut input),
+            Err(ParseError::Error(ExpectedIdent {
+                span: Span::new(0, 0)
+            }))
+        );
+    }
+}
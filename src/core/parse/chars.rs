@@ -0,0 +1,190 @@
+use token_precedence::span::{Span, Spanned};
+
+use crate::{
+    input::{Input, InputExt},
+    parse::{IsParse, ParseError, RichError},
+};
+
+/// Matches a char for which `.0` returns true
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub struct Satisfy<F: Fn(char) -> bool>(pub F);
+
+/// Matches any char of `.0`
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub struct OneOf<'s>(pub &'s str);
+
+/// Matches any char not in `.0`
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub struct NoneOf<'s>(pub &'s str);
+
+/// Matches any char
+#[derive(Default, Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub struct AnyChar;
+
+/// The chars a char parser expected
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub enum CharSet<'s> {
+    Satisfy,
+    OneOf(&'s str),
+    NoneOf(&'s str),
+}
+
+/// `found` at `span` is not in `expected`
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub struct ExpectedChar<'s> {
+    pub expected: CharSet<'s>,
+    pub found: char,
+    pub span: Span,
+}
+
+#[inline(always)]
+fn match_char<'s, I: ?Sized + Input>(
+    input: &mut I,
+    expected: CharSet<'s>,
+    func: impl FnOnce(char) -> bool,
+) -> Result<Spanned<char>, ParseError<ExpectedChar<'s>>> {
+    let entry = input.peek_entry()?;
+    let span = entry.span();
+    let found = entry.get();
+
+    if !func(found) {
+        return Err(ParseError::Error(ExpectedChar {
+            expected,
+            found,
+            span,
+        }));
+    }
+
+    entry.consume();
+    Ok(span.over(found))
+}
+
+impl<'a, F: Fn(char) -> bool> IsParse<'a> for Satisfy<F> {
+    type Output = Spanned<char>;
+    type Error = ExpectedChar<'static>;
+
+    #[inline(always)]
+    fn __parse<I: ?Sized + Input>(
+        self,
+        input: &'a mut I,
+    ) -> Result<Self::Output, ParseError<Self::Error>> {
+        match_char(input, CharSet::Satisfy, self.0)
+    }
+}
+
+impl<'a, 's> IsParse<'a> for OneOf<'s> {
+    type Output = Spanned<char>;
+    type Error = ExpectedChar<'s>;
+
+    #[inline(always)]
+    fn __parse<I: ?Sized + Input>(
+        self,
+        input: &'a mut I,
+    ) -> Result<Self::Output, ParseError<Self::Error>> {
+        match_char(input, CharSet::OneOf(self.0), |c| self.0.contains(c))
+    }
+}
+
+impl<'a, 's> IsParse<'a> for NoneOf<'s> {
+    type Output = Spanned<char>;
+    type Error = ExpectedChar<'s>;
+
+    #[inline(always)]
+    fn __parse<I: ?Sized + Input>(
+        self,
+        input: &'a mut I,
+    ) -> Result<Self::Output, ParseError<Self::Error>> {
+        match_char(input, CharSet::NoneOf(self.0), |c| !self.0.contains(c))
+    }
+}
+
+impl<'a> IsParse<'a> for AnyChar {
+    type Output = Spanned<char>;
+    type Error = !;
+
+    #[inline(always)]
+    fn __parse<I: ?Sized + Input>(
+        self,
+        input: &'a mut I,
+    ) -> Result<Self::Output, ParseError<Self::Error>> {
+        let entry = input.peek_entry()?;
+        let span = entry.span();
+        let found = entry.get();
+
+        entry.consume();
+        Ok(span.over(found))
+    }
+}
+
+impl From<ExpectedChar<'_>> for RichError {
+    fn from(value: ExpectedChar<'_>) -> Self {
+        // Expected at the found char, like the errors of other parsers that consume nothing
+        let span = Span::new(value.span.start(), value.span.start());
+        let err = RichError::new(span).found(format!("`{}`", value.found));
+
+        match value.expected {
+            CharSet::Satisfy => err.expecting("char"),
+            CharSet::OneOf(chars) => chars
+                .chars()
+                .fold(err, |err, c| err.expecting(format!("`{c}`"))),
+            CharSet::NoneOf(chars) => err.expecting(format!("char not in `{chars}`")),
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::{
+        input::{ReadError, ReaderInput, StrView},
+        parse::Parse,
+    };
+
+    use super::*;
+
+    #[test]
+    fn sets() {
+        let mut input = StrView::new("+é1");
+
+        assert_eq!(OneOf("-+").parse(&mut input), Ok(Span::new(0, 1).over('+')));
+        assert_eq!(
+            OneOf("-+").parse(&mut input),
+            Err(ParseError::Error(ExpectedChar {
+                expected: CharSet::OneOf("-+"),
+                found: 'é',
+                span: Span::new(1, 3)
+            }))
+        );
+        assert_eq!(
+            NoneOf("0123456789").parse(&mut input),
+            Ok(Span::new(1, 3).over('é'))
+        );
+        assert_eq!(
+            NoneOf("0123456789").parse(&mut input),
+            Err(ParseError::Error(ExpectedChar {
+                expected: CharSet::NoneOf("0123456789"),
+                found: '1',
+                span: Span::new(3, 4)
+            }))
+        );
+        assert_eq!(
+            Satisfy(|c| c.is_ascii_digit()).parse(&mut input),
+            Ok(Span::new(3, 4).over('1'))
+        );
+        assert_eq!(
+            AnyChar.parse(&mut input),
+            Err(ParseError::ReadError(ReadError::EOF))
+        );
+    }
+
+    #[test]
+    fn end_of_reader() {
+        let mut input = ReaderInput::<_, 16>::new("ab".as_bytes());
+
+        assert_eq!(AnyChar.parse(&mut input), Ok(Span::new(0, 1).over('a')));
+        assert_eq!(AnyChar.parse(&mut input), Ok(Span::new(1, 2).over('b')));
+        assert_eq!(
+            AnyChar.parse(&mut input),
+            Err(ParseError::ReadError(ReadError::EOF))
+        );
+    }
+}
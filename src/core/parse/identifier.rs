@@ -0,0 +1,413 @@
+use std::{borrow::Cow, cmp::Ordering};
+
+use token_precedence::span::{Span, Spanned};
+
+use crate::{
+    input::{Input, InputExt},
+    parse::{IsParse, ParseError, RichError},
+};
+
+#[rustfmt::skip]
+mod tables;
+
+/// Parses an identifier following UAX #31, a XID_Start char followed by XID_Continue chars
+///
+/// # Note
+/// `_` is not XID_Start, so most languages want `Identifier::new().with_start("_")`.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub struct Identifier<'e> {
+    /// Extra chars allowed anywhere in the identifier
+    pub extra_start: &'e str,
+    /// Extra chars allowed after the first char
+    pub extra_continue: &'e str,
+    /// Rejects identifiers that are not in Normalization Form C
+    pub nfc: bool,
+}
+
+impl<'e> Identifier<'e> {
+    #[inline(always)]
+    pub const fn new() -> Self {
+        Self {
+            extra_start: "",
+            extra_continue: "",
+            nfc: false,
+        }
+    }
+
+    #[inline(always)]
+    pub const fn with_start(mut self, extra: &'e str) -> Self {
+        self.extra_start = extra;
+        self
+    }
+
+    #[inline(always)]
+    pub const fn with_continue(mut self, extra: &'e str) -> Self {
+        self.extra_continue = extra;
+        self
+    }
+
+    #[inline(always)]
+    pub const fn check_nfc(mut self) -> Self {
+        self.nfc = true;
+        self
+    }
+
+    #[inline(always)]
+    fn is_start(&self, c: char) -> bool {
+        is_xid_start(c) || self.extra_start.contains(c)
+    }
+
+    #[inline(always)]
+    fn is_continue(&self, c: char) -> bool {
+        is_xid_continue(c) || self.extra_start.contains(c) || self.extra_continue.contains(c)
+    }
+}
+
+impl Default for Identifier<'_> {
+    #[inline(always)]
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub enum IdentifierErrorKind {
+    /// No start char was found
+    Expected,
+    /// The identifier is not in Normalization Form C
+    NotNfc,
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub struct IdentifierError {
+    pub kind: IdentifierErrorKind,
+    pub span: Span,
+}
+
+#[inline(always)]
+fn in_table(table: &[(u32, u32)], c: char) -> bool {
+    let c = c as u32;
+
+    table
+        .binary_search_by(|&(start, end)| {
+            if end < c {
+                Ordering::Less
+            } else if start > c {
+                Ordering::Greater
+            } else {
+                Ordering::Equal
+            }
+        })
+        .is_ok()
+}
+
+#[inline(always)]
+pub fn is_xid_start(c: char) -> bool {
+    if c.is_ascii() {
+        c.is_ascii_alphabetic()
+    } else {
+        in_table(tables::XID_START, c)
+    }
+}
+
+#[inline(always)]
+pub fn is_xid_continue(c: char) -> bool {
+    if c.is_ascii() {
+        c.is_ascii_alphanumeric() || c == '_'
+    } else {
+        in_table(tables::XID_CONTINUE, c)
+    }
+}
+
+/// The Canonical_Combining_Class of `c`, 0 for starters
+fn combining_class(c: char) -> u8 {
+    let code = c as u32;
+
+    tables::COMBINING_CLASSES
+        .binary_search_by(|&(start, end, _)| {
+            if end < code {
+                Ordering::Less
+            } else if start > code {
+                Ordering::Greater
+            } else {
+                Ordering::Equal
+            }
+        })
+        .map_or(0, |i| tables::COMBINING_CLASSES[i].2)
+}
+
+const S_BASE: u32 = 0xAC00;
+const L_BASE: u32 = 0x1100;
+const V_BASE: u32 = 0x1161;
+const T_BASE: u32 = 0x11A7;
+const L_COUNT: u32 = 19;
+const V_COUNT: u32 = 21;
+const T_COUNT: u32 = 28;
+const S_COUNT: u32 = L_COUNT * V_COUNT * T_COUNT;
+
+/// Pushes the full canonical decomposition of `c` to `out`
+fn decompose(c: char, out: &mut Vec<char>) {
+    let code = c as u32;
+
+    if (S_BASE..S_BASE + S_COUNT).contains(&code) {
+        let index = code - S_BASE;
+        let l = L_BASE + index / (V_COUNT * T_COUNT);
+        let v = V_BASE + index % (V_COUNT * T_COUNT) / T_COUNT;
+        let t = T_BASE + index % T_COUNT;
+
+        out.extend([l, v].map(|code| char::from_u32(code).expect("Hangul jamo")));
+
+        if t != T_BASE {
+            out.push(char::from_u32(t).expect("Hangul jamo"));
+        }
+
+        return;
+    }
+
+    match tables::DECOMPOSITIONS.binary_search_by_key(&c, |&(c, _)| c) {
+        Ok(i) => out.extend(tables::DECOMPOSITIONS[i].1.chars()),
+        Err(_) => out.push(c),
+    }
+}
+
+/// The primary composite of `first` followed by `second`, if any
+fn compose(first: char, second: char) -> Option<char> {
+    let (first_code, second_code) = (first as u32, second as u32);
+
+    // Hangul leading and vowel jamo, or LV syllables and trailing jamo
+    if (L_BASE..L_BASE + L_COUNT).contains(&first_code)
+        && (V_BASE..V_BASE + V_COUNT).contains(&second_code)
+    {
+        let index = (first_code - L_BASE) * V_COUNT + second_code - V_BASE;
+        return char::from_u32(S_BASE + index * T_COUNT);
+    }
+
+    if (S_BASE..S_BASE + S_COUNT).contains(&first_code)
+        && (first_code - S_BASE).is_multiple_of(T_COUNT)
+        && (T_BASE + 1..T_BASE + T_COUNT).contains(&second_code)
+    {
+        return char::from_u32(first_code + second_code - T_BASE);
+    }
+
+    tables::COMPOSITIONS
+        .binary_search_by_key(&(first, second), |&(first, second, _)| (first, second))
+        .ok()
+        .map(|i| tables::COMPOSITIONS[i].2)
+}
+
+/// Normalizes `s` to Normalization Form C
+fn nfc(s: &str) -> Vec<char> {
+    let mut chars = Vec::with_capacity(s.len());
+
+    for c in s.chars() {
+        decompose(c, &mut chars);
+    }
+
+    // Canonical ordering, a stable sort of each run of non-starters by combining class
+    let mut start = 0;
+
+    while start < chars.len() {
+        if combining_class(chars[start]) == 0 {
+            start += 1;
+            continue;
+        }
+
+        let end = chars[start..]
+            .iter()
+            .position(|&c| combining_class(c) == 0)
+            .map_or(chars.len(), |len| start + len);
+
+        chars[start..end].sort_by_key(|&c| combining_class(c));
+        start = end;
+    }
+
+    let mut composed: Vec<char> = Vec::with_capacity(chars.len());
+    let mut starter = None;
+    let mut last_class = 0;
+
+    for c in chars {
+        let class = combining_class(c);
+
+        // A char composes with the last starter unless a char in between blocks it
+        if let Some(starter) = starter
+            && (composed.len() == starter + 1 || last_class < class)
+            && let Some(composite) = compose(composed[starter], c)
+        {
+            composed[starter] = composite;
+            continue;
+        }
+
+        if class == 0 {
+            starter = Some(composed.len());
+        }
+
+        last_class = class;
+        composed.push(c);
+    }
+
+    composed
+}
+
+/// Checks `s` is in Normalization Form C
+///
+/// # Note
+/// The NFC_Quick_Check property and canonical ordering settle most strings,
+/// only strings with a `Maybe` char are normalized in full to be sure.
+pub fn is_nfc(s: &str) -> bool {
+    let mut maybe = false;
+    let mut prev_class = 0;
+
+    for c in s.chars() {
+        if c.is_ascii() {
+            prev_class = 0;
+            continue;
+        }
+
+        let class = combining_class(c);
+
+        if class != 0 && prev_class > class {
+            return false;
+        }
+
+        if in_table(tables::NFC_NO, c) {
+            return false;
+        }
+
+        maybe |= in_table(tables::NFC_MAYBE, c);
+        prev_class = class;
+    }
+
+    !maybe || s.chars().eq(nfc(s))
+}
+
+impl<'a, 'e> IsParse<'a> for Identifier<'e> {
+    type Output = Spanned<Cow<'a, str>>;
+    type Error = IdentifierError;
+
+    fn __parse<I: ?Sized + Input>(
+        self,
+        input: &'a mut I,
+    ) -> Result<Self::Output, ParseError<Self::Error>> {
+        let mut first = true;
+
+        let entry = input.read_until_entry(16, |c| {
+            let valid = if first {
+                self.is_start(c)
+            } else {
+                self.is_continue(c)
+            };
+            first = false;
+
+            !valid
+        })?;
+
+        let span = entry.span();
+
+        let kind = if entry.get().is_empty() {
+            IdentifierErrorKind::Expected
+        } else if self.nfc && !is_nfc(entry.get()) {
+            IdentifierErrorKind::NotNfc
+        } else {
+            return Ok(span.over(entry.consume_str()));
+        };
+
+        Err(ParseError::Error(IdentifierError { kind, span }))
+    }
+}
+
+impl From<IdentifierError> for RichError {
+    #[inline(always)]
+    fn from(value: IdentifierError) -> Self {
+        RichError::new(value.span).expecting(match value.kind {
+            IdentifierErrorKind::Expected => "identifier",
+            IdentifierErrorKind::NotNfc => "identifier in NFC",
+        })
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::{
+        input::StrView,
+        parse::{Parse, ParseExt},
+    };
+
+    use super::*;
+
+    fn parse(identifier: Identifier, s: &str) -> Result<String, IdentifierErrorKind> {
+        identifier
+            .mapped(|output: Spanned<Cow<str>>| output.inner.into_owned())
+            .parse(&mut StrView::new(s))
+            .map_err(|err| match err {
+                ParseError::Error(err) => err.kind,
+                ParseError::ReadError(err) => panic!("{err:?}"),
+            })
+    }
+
+    #[test]
+    fn xid() {
+        assert!(is_xid_start('a'));
+        assert!(is_xid_start('é'));
+        assert!(is_xid_start('字'));
+        assert!(!is_xid_start('_'));
+        assert!(!is_xid_start('1'));
+        assert!(!is_xid_start('²'));
+        assert!(is_xid_continue('1'));
+        assert!(is_xid_continue('_'));
+        assert!(is_xid_continue('\u{0301}'));
+        assert!(!is_xid_continue('-'));
+        assert!(!is_xid_continue('😀'));
+    }
+
+    #[test]
+    fn identifiers() {
+        let identifier = Identifier::new().with_start("_").with_continue("$");
+
+        assert_eq!(parse(identifier, "café+1"), Ok("café".to_string()));
+        assert_eq!(parse(identifier, "_x$1 y"), Ok("_x$1".to_string()));
+        assert_eq!(parse(identifier, "$x"), Err(IdentifierErrorKind::Expected));
+        assert_eq!(parse(identifier, "1x"), Err(IdentifierErrorKind::Expected));
+        assert_eq!(
+            parse(Identifier::new(), "_x"),
+            Err(IdentifierErrorKind::Expected)
+        );
+
+        let mut input = StrView::new("ab");
+        assert_eq!(
+            Identifier::new()
+                .parse(&mut input)
+                .map(|output| output.span),
+            Ok(Span::new(0, 2))
+        );
+    }
+
+    #[test]
+    fn nfc() {
+        let identifier = Identifier::new().check_nfc();
+
+        assert!(is_nfc("caf\u{e9}"));
+        assert!(!is_nfc("cafe\u{301}"));
+        assert!(!is_nfc("\u{212B}"));
+        assert!(!is_nfc("\u{1100}\u{1161}"));
+        assert!(is_nfc("\u{AC00}"));
+        assert!(is_nfc("x\u{323}\u{301}"));
+        assert!(!is_nfc("x\u{301}\u{323}"));
+        assert!(is_nfc("\u{1EA1}\u{301}"));
+        assert!(!is_nfc("\u{1EA1}\u{302}"));
+        assert!(!is_nfc("a\u{323}\u{302}"));
+        assert!(is_nfc("\u{1100}\u{300}\u{1161}"));
+        assert!(!is_nfc("\u{AC00}\u{11A8}"));
+        assert!(!is_nfc("\u{B47}\u{B3E}"));
+        assert!(is_nfc("\u{B47}\u{301}\u{B3E}"));
+
+        assert_eq!(parse(identifier, "caf\u{e9}"), Ok("caf\u{e9}".to_string()));
+        assert_eq!(
+            parse(identifier, "cafe\u{301}"),
+            Err(IdentifierErrorKind::NotNfc)
+        );
+        assert_eq!(
+            parse(Identifier::new(), "cafe\u{301}"),
+            Ok("cafe\u{301}".to_string())
+        );
+    }
+}
@@ -0,0 +1,250 @@
+use token_precedence::span::{Span, Spanned};
+
+use crate::{
+    input::{Entry, Input},
+    parse::{IsParse, ParseError, RichError},
+};
+
+use super::read_ahead;
+
+/// A trie of literals that matches the longest literal at the current position
+///
+/// # Note
+/// Parse with `&LiteralSet` so the set can be reused.
+#[derive(Debug, Clone, PartialEq, Eq, Hash)]
+pub struct LiteralSet<V> {
+    nodes: Vec<Node<V>>,
+    max_len: usize,
+}
+
+#[derive(Debug, Clone, PartialEq, Eq, Hash)]
+struct Node<V> {
+    /// Sorted by byte
+    children: Vec<(u8, usize)>,
+    value: Option<V>,
+}
+
+impl<V> Node<V> {
+    #[inline(always)]
+    const fn new() -> Self {
+        Node {
+            children: Vec::new(),
+            value: None,
+        }
+    }
+
+    #[inline(always)]
+    fn child(&self, byte: u8) -> Result<usize, usize> {
+        self.children
+            .binary_search_by_key(&byte, |&(b, _)| b)
+            .map(|i| self.children[i].1)
+    }
+}
+
+/// No literal of the set was found at `span`
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub struct ExpectedLiteral {
+    pub span: Span,
+}
+
+impl<V> LiteralSet<V> {
+    #[inline(always)]
+    pub fn new() -> Self {
+        LiteralSet {
+            nodes: vec![Node::new()],
+            max_len: 0,
+        }
+    }
+
+    /// # Panics
+    /// Panics if `literal` is already in the set
+    pub fn insert(&mut self, literal: &str, value: V) {
+        let mut node = 0;
+
+        for &byte in literal.as_bytes() {
+            node = match self.nodes[node].child(byte) {
+                Ok(child) => child,
+                Err(i) => {
+                    let child = self.nodes.len();
+                    self.nodes.push(Node::new());
+                    self.nodes[node].children.insert(i, (byte, child));
+                    child
+                }
+            };
+        }
+
+        assert!(
+            self.nodes[node].value.is_none(),
+            "duplicate literal `{literal}`"
+        );
+
+        self.nodes[node].value = Some(value);
+        self.max_len = self.max_len.max(literal.len());
+    }
+
+    /// The value of `literal` if it is in the set
+    pub fn get(&self, literal: &str) -> Option<&V> {
+        let mut node = 0;
+
+        for &byte in literal.as_bytes() {
+            node = self.nodes[node].child(byte).ok()?;
+        }
+
+        self.nodes[node].value.as_ref()
+    }
+
+    /// Finds the longest literal that `read` starts with, giving its length and value
+    fn longest_match(&self, read: &str) -> Option<(usize, &V)> {
+        let mut node = 0;
+        let mut longest = self.nodes[0].value.as_ref().map(|value| (0, value));
+
+        for (i, &byte) in read.as_bytes().iter().enumerate() {
+            match self.nodes[node].child(byte) {
+                Ok(child) => node = child,
+                Err(_) => break,
+            }
+
+            if let Some(value) = &self.nodes[node].value {
+                longest = Some((i + 1, value));
+            }
+        }
+
+        longest
+    }
+}
+
+impl<V> Default for LiteralSet<V> {
+    #[inline(always)]
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+impl<'s, V> FromIterator<(&'s str, V)> for LiteralSet<V> {
+    fn from_iter<T: IntoIterator<Item = (&'s str, V)>>(iter: T) -> Self {
+        let mut set = LiteralSet::new();
+
+        for (literal, value) in iter {
+            set.insert(literal, value);
+        }
+
+        set
+    }
+}
+
+impl<'s, V, const N: usize> From<[(&'s str, V); N]> for LiteralSet<V> {
+    #[inline(always)]
+    fn from(value: [(&'s str, V); N]) -> Self {
+        value.into_iter().collect()
+    }
+}
+
+impl<'a, V: Clone> IsParse<'a> for &LiteralSet<V> {
+    type Output = Spanned<V>;
+    type Error = ExpectedLiteral;
+
+    fn __parse<I: ?Sized + Input>(
+        self,
+        input: &'a mut I,
+    ) -> Result<Self::Output, ParseError<Self::Error>> {
+        let Some((len, value)) = self.longest_match(read_ahead(input, self.max_len)?) else {
+            return Err(ParseError::Error(ExpectedLiteral {
+                span: Span::new(input.index(), input.index()),
+            }));
+        };
+
+        // Every literal is a whole str so the match ends on a char boundary
+        let entry = unsafe { Entry::new(input, len) };
+        let span = entry.span();
+        entry.consume();
+
+        Ok(span.over(value.clone()))
+    }
+}
+
+impl From<ExpectedLiteral> for RichError {
+    #[inline(always)]
+    fn from(value: ExpectedLiteral) -> Self {
+        RichError::new(value.span).expecting("literal")
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::{
+        core::trim::TrimWhitespace,
+        input::{ReaderInput, StrView},
+        parse::{Parse, ParseIter},
+    };
+
+    use super::*;
+
+    #[derive(Debug, Clone, Copy, PartialEq, Eq)]
+    enum Op {
+        Lt,
+        Le,
+        Shl,
+        ShlAssign,
+        Arrow,
+    }
+
+    fn ops() -> LiteralSet<Op> {
+        LiteralSet::from([
+            ("<", Op::Lt),
+            ("<=", Op::Le),
+            ("<<", Op::Shl),
+            ("<<=", Op::ShlAssign),
+            ("→", Op::Arrow),
+        ])
+    }
+
+    #[test]
+    fn maximal_munch() {
+        let ops = ops();
+        let mut input = StrView::new("<<= << <= < <<<→");
+
+        let parsed = ParseIter::new(&mut input, TrimWhitespace, &ops)
+            .unwrap()
+            .map(|op| op.unwrap().inner)
+            .collect::<Vec<_>>();
+
+        assert_eq!(
+            parsed,
+            [
+                Op::ShlAssign,
+                Op::Shl,
+                Op::Le,
+                Op::Lt,
+                Op::Shl,
+                Op::Lt,
+                Op::Arrow
+            ]
+        );
+    }
+
+    #[test]
+    fn spans() {
+        let ops = ops();
+        let mut input = ReaderInput::<_, 16>::new("→<=+".as_bytes());
+
+        assert_eq!(
+            (&ops).parse(&mut input),
+            Ok(Span::new(0, 3).over(Op::Arrow))
+        );
+        assert_eq!((&ops).parse(&mut input), Ok(Span::new(3, 5).over(Op::Le)));
+        assert_eq!(
+            (&ops).parse(&mut input),
+            Err(ParseError::Error(ExpectedLiteral {
+                span: Span::new(5, 5)
+            }))
+        );
+        assert_eq!(ops.get("<<"), Some(&Op::Shl));
+        assert_eq!(ops.get("<<<"), None);
+    }
+
+    #[test]
+    #[should_panic = "duplicate literal `<`"]
+    fn duplicate() {
+        let _ = LiteralSet::from([("<", Op::Lt), ("<", Op::Le)]);
+    }
+}
@@ -0,0 +1,416 @@
+use std::{borrow::Cow, marker::PhantomData};
+
+use token_precedence::span::{Span, Spanned};
+
+use crate::{
+    input::{Input, InputExt},
+    parse::{IsParse, ParseError, RichError},
+};
+
+mod big_int;
+pub use big_int::BigInt;
+
+/// Parses a decimal, `0x` hex, `0o` octal or `0b` binary integer into `T`
+/// with an optional sign and `_` separators
+///
+/// # Note
+/// A literal runs over every alphanumeric char and `_`, so `12ab` is malformed rather than `12`.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub struct Integer<T>(PhantomData<fn() -> T>);
+
+impl<T> Integer<T> {
+    #[inline(always)]
+    pub const fn new() -> Self {
+        Integer(PhantomData)
+    }
+}
+
+impl<T> Default for Integer<T> {
+    #[inline(always)]
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+/// Parses a decimal float into `T` with an optional sign, fraction, exponent and `_` separators
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub struct Float<T>(PhantomData<fn() -> T>);
+
+impl<T> Float<T> {
+    #[inline(always)]
+    pub const fn new() -> Self {
+        Float(PhantomData)
+    }
+}
+
+impl<T> Default for Float<T> {
+    #[inline(always)]
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub enum NumberErrorKind {
+    /// No digit was found
+    Expected,
+    /// The literal has invalid digits or is missing digits
+    Malformed,
+    /// The literal does not fit in the target type
+    Overflow,
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub struct NumberError {
+    pub kind: NumberErrorKind,
+    pub span: Span,
+}
+
+/// A type that an integer literal can be parsed into
+pub trait IntegerLiteral: Sized {
+    /// Builds the value from `digits` (most significant first) in `radix`,
+    /// returning `None` if it does not fit
+    fn from_digits(
+        negative: bool,
+        radix: u32,
+        digits: impl IntoIterator<Item = u32>,
+    ) -> Option<Self>;
+}
+
+/// A type that a float literal can be parsed into
+pub trait FloatLiteral: Sized {
+    /// Builds the value from a valid decimal float literal without separators,
+    /// returning `None` if it does not fit
+    fn from_literal(literal: &str) -> Option<Self>;
+}
+
+#[inline(always)]
+fn magnitude(radix: u32, digits: impl IntoIterator<Item = u32>) -> Option<u128> {
+    digits.into_iter().try_fold(0u128, |acc, digit| {
+        acc.checked_mul(radix as u128)?.checked_add(digit as u128)
+    })
+}
+
+macro_rules! impl_unsigned {
+    ($($ty:ty),+) => {
+        $(
+            impl IntegerLiteral for $ty {
+                #[inline(always)]
+                fn from_digits(
+                    negative: bool,
+                    radix: u32,
+                    digits: impl IntoIterator<Item = u32>,
+                ) -> Option<Self> {
+                    match magnitude(radix, digits)? {
+                        0 => Some(0),
+                        _ if negative => None,
+                        magnitude => magnitude.try_into().ok(),
+                    }
+                }
+            }
+        )+
+    };
+}
+
+macro_rules! impl_signed {
+    ($($ty:ty => $unsigned:ty),+) => {
+        $(
+            impl IntegerLiteral for $ty {
+                #[inline(always)]
+                fn from_digits(
+                    negative: bool,
+                    radix: u32,
+                    digits: impl IntoIterator<Item = u32>,
+                ) -> Option<Self> {
+                    let magnitude = magnitude(radix, digits)?;
+
+                    if negative {
+                        // `MIN` has a magnitude one larger than `MAX`
+                        (magnitude <= <$ty>::MIN.unsigned_abs() as u128)
+                            .then(|| (magnitude as $unsigned as $ty).wrapping_neg())
+                    } else {
+                        magnitude.try_into().ok()
+                    }
+                }
+            }
+        )+
+    };
+}
+
+impl_unsigned!(u8, u16, u32, u64, u128, usize);
+impl_signed!(i8 => u8, i16 => u16, i32 => u32, i64 => u64, i128 => u128, isize => usize);
+
+macro_rules! impl_float {
+    ($($ty:ty),+) => {
+        $(
+            impl FloatLiteral for $ty {
+                #[inline(always)]
+                fn from_literal(literal: &str) -> Option<Self> {
+                    literal.parse::<$ty>().ok().filter(|value| value.is_finite())
+                }
+            }
+        )+
+    };
+}
+
+impl_float!(f32, f64);
+
+/// Splits a signed integer literal into its sign, radix and digits
+fn split_integer(literal: &str) -> Option<(bool, u32, &str)> {
+    let (negative, unsigned) = split_sign(literal);
+
+    let (radix, digits) = match unsigned.get(..2) {
+        Some("0x" | "0X") => (16, &unsigned[2..]),
+        Some("0o" | "0O") => (8, &unsigned[2..]),
+        Some("0b" | "0B") => (2, &unsigned[2..]),
+        _ => (10, unsigned),
+    };
+
+    let valid =
+        digits.chars().any(|c| c != '_') && digits.chars().all(|c| c == '_' || c.is_digit(radix));
+
+    valid.then_some((negative, radix, digits))
+}
+
+/// Checks the literal is `digits ('.' digits)? ([eE] sign? digits)?`
+fn is_float(literal: &str) -> bool {
+    fn digits(part: &str) -> bool {
+        part.starts_with(|c: char| c.is_ascii_digit())
+            && part.chars().all(|c| c == '_' || c.is_ascii_digit())
+    }
+
+    let (_, unsigned) = split_sign(literal);
+
+    let (mantissa, exponent) = match unsigned.split_once(['e', 'E']) {
+        Some((mantissa, exponent)) => (mantissa, Some(split_sign(exponent).1)),
+        None => (unsigned, None),
+    };
+
+    let valid_mantissa = match mantissa.split_once('.') {
+        Some((int, fraction)) => digits(int) && digits(fraction),
+        None => digits(mantissa),
+    };
+
+    valid_mantissa && exponent.is_none_or(digits)
+}
+
+#[inline(always)]
+fn split_sign(literal: &str) -> (bool, &str) {
+    match literal.as_bytes().first() {
+        Some(b'-') => (true, &literal[1..]),
+        Some(b'+') => (false, &literal[1..]),
+        _ => (false, literal),
+    }
+}
+
+#[inline(always)]
+fn is_number(literal: &str) -> bool {
+    split_sign(literal)
+        .1
+        .starts_with(|c: char| c.is_ascii_digit())
+}
+
+#[inline(always)]
+fn number_error<T>(kind: NumberErrorKind, span: Span) -> Result<T, ParseError<NumberError>> {
+    Err(ParseError::Error(NumberError { kind, span }))
+}
+
+impl<'a, T: IntegerLiteral> IsParse<'a> for Integer<T> {
+    type Output = Spanned<T>;
+    type Error = NumberError;
+
+    fn __parse<I: ?Sized + Input>(
+        self,
+        input: &'a mut I,
+    ) -> Result<Self::Output, ParseError<Self::Error>> {
+        let mut first = true;
+
+        let entry = input.read_until_entry(16, |c| {
+            let sign = first && (c == '+' || c == '-');
+            first = false;
+
+            !(sign || c.is_ascii_alphanumeric() || c == '_')
+        })?;
+
+        let index = entry.input().index();
+        let span = entry.span();
+
+        if !is_number(entry.get()) {
+            return number_error(NumberErrorKind::Expected, Span::new(index, index));
+        }
+
+        let Some((negative, radix, digits)) = split_integer(entry.get()) else {
+            return number_error(NumberErrorKind::Malformed, span);
+        };
+
+        let digits = digits.chars().filter_map(|c| c.to_digit(radix));
+
+        let Some(value) = T::from_digits(negative, radix, digits) else {
+            return number_error(NumberErrorKind::Overflow, span);
+        };
+
+        entry.consume();
+        Ok(span.over(value))
+    }
+}
+
+impl<'a, T: FloatLiteral> IsParse<'a> for Float<T> {
+    type Output = Spanned<T>;
+    type Error = NumberError;
+
+    fn __parse<I: ?Sized + Input>(
+        self,
+        input: &'a mut I,
+    ) -> Result<Self::Output, ParseError<Self::Error>> {
+        let mut prev = None;
+        let mut seen_dot = false;
+
+        let entry = input.read_until_entry(16, |c| {
+            let sign = (c == '+' || c == '-') && matches!(prev, None | Some('e' | 'E'));
+            let dot = c == '.' && !seen_dot;
+            seen_dot |= dot;
+            prev = Some(c);
+
+            !(sign || dot || c.is_ascii_alphanumeric() || c == '_')
+        })?;
+
+        let index = entry.input().index();
+        let span = entry.span();
+
+        if !is_number(entry.get()) {
+            return number_error(NumberErrorKind::Expected, Span::new(index, index));
+        }
+
+        if !is_float(entry.get()) {
+            return number_error(NumberErrorKind::Malformed, span);
+        }
+
+        let literal = match entry.get() {
+            literal if literal.contains('_') => Cow::Owned(literal.replace('_', "")),
+            literal => Cow::Borrowed(literal),
+        };
+
+        let Some(value) = T::from_literal(&literal) else {
+            return number_error(NumberErrorKind::Overflow, span);
+        };
+
+        entry.consume();
+        Ok(span.over(value))
+    }
+}
+
+impl From<NumberError> for RichError {
+    #[inline(always)]
+    fn from(value: NumberError) -> Self {
+        RichError::new(value.span).expecting(match value.kind {
+            NumberErrorKind::Expected => "number",
+            NumberErrorKind::Malformed => "well-formed number",
+            NumberErrorKind::Overflow => "number in range",
+        })
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::{
+        core::trim::TrimWhitespace,
+        input::{ReaderInput, StrView},
+        parse::{Parse, ParseIter, ParseIterError},
+    };
+
+    use super::*;
+
+    fn integer<T: IntegerLiteral>(literal: &str) -> Result<T, NumberErrorKind> {
+        Integer::<T>::new()
+            .parse(&mut StrView::new(literal))
+            .map(|value| value.inner)
+            .map_err(|err| match err {
+                ParseError::Error(err) => err.kind,
+                ParseError::ReadError(err) => panic!("{err:?}"),
+            })
+    }
+
+    fn float<T: FloatLiteral>(literal: &str) -> Result<T, NumberErrorKind> {
+        Float::<T>::new()
+            .parse(&mut StrView::new(literal))
+            .map(|value| value.inner)
+            .map_err(|err| match err {
+                ParseError::Error(err) => err.kind,
+                ParseError::ReadError(err) => panic!("{err:?}"),
+            })
+    }
+
+    #[test]
+    fn integers() {
+        assert_eq!(integer::<u32>("1_000_000"), Ok(1_000_000));
+        assert_eq!(integer::<u8>("0xff"), Ok(255));
+        assert_eq!(integer::<u8>("0o17"), Ok(15));
+        assert_eq!(integer::<i16>("-0b1010"), Ok(-10));
+        assert_eq!(integer::<i8>("-128"), Ok(i8::MIN));
+        assert_eq!(integer::<i128>("+0x_7fff"), Ok(0x7fff));
+        assert_eq!(integer::<u128>(&u128::MAX.to_string()), Ok(u128::MAX));
+        assert_eq!(integer::<u32>("-0"), Ok(0));
+    }
+
+    #[test]
+    fn integer_errors() {
+        assert_eq!(integer::<u8>("256"), Err(NumberErrorKind::Overflow));
+        assert_eq!(integer::<i8>("-129"), Err(NumberErrorKind::Overflow));
+        assert_eq!(integer::<u8>("-1"), Err(NumberErrorKind::Overflow));
+        assert_eq!(integer::<u32>("0x"), Err(NumberErrorKind::Malformed));
+        assert_eq!(integer::<u32>("0b102"), Err(NumberErrorKind::Malformed));
+        assert_eq!(integer::<u32>("12ab"), Err(NumberErrorKind::Malformed));
+        assert_eq!(integer::<u32>("-x"), Err(NumberErrorKind::Expected));
+        assert_eq!(integer::<u32>("_1"), Err(NumberErrorKind::Expected));
+
+        let mut input = StrView::new("1 0x1g");
+        let integers = ParseIter::new(&mut input, TrimWhitespace, Integer::<u32>::new())
+            .unwrap()
+            .take(2)
+            .collect::<Vec<_>>();
+
+        assert_eq!(
+            integers,
+            [
+                Ok(Span::new(0, 1).over(1)),
+                Err(ParseIterError::Error(NumberError {
+                    kind: NumberErrorKind::Malformed,
+                    span: Span::new(2, 6)
+                }))
+            ]
+        );
+        assert_eq!(input.read(), "0x1g");
+    }
+
+    #[test]
+    fn floats() {
+        assert_eq!(float::<f64>("1.5"), Ok(1.5));
+        assert_eq!(float::<f64>("-2_000.25e-3"), Ok(-2.00025));
+        assert_eq!(float::<f32>("3E2"), Ok(300.0));
+        assert_eq!(float::<f64>("7"), Ok(7.0));
+        assert_eq!(float::<f32>("1e39"), Err(NumberErrorKind::Overflow));
+        assert_eq!(float::<f64>("1."), Err(NumberErrorKind::Malformed));
+        assert_eq!(float::<f64>("1e"), Err(NumberErrorKind::Malformed));
+        assert_eq!(float::<f64>(".5"), Err(NumberErrorKind::Expected));
+
+        let mut input = StrView::new("1.5..");
+        assert_eq!(
+            Float::<f64>::new().parse(&mut input),
+            Ok(Span::new(0, 3).over(1.5))
+        );
+        assert_eq!(input.read(), "..");
+    }
+
+    #[test]
+    fn big_integers() {
+        let mut input =
+            ReaderInput::<_, 64>::new("-0x1_0000_0000_0000_0000_0000_0000_0000_0000".as_bytes());
+
+        let big = Integer::<BigInt>::new().parse(&mut input).unwrap().inner;
+
+        assert!(big.is_negative());
+        assert_eq!(big.to_string(), "-340282366920938463463374607431768211456");
+        assert_eq!(big.to_i128(), None);
+        assert_eq!(integer::<BigInt>("-42").unwrap().to_i128(), Some(-42));
+    }
+}
@@ -0,0 +1,175 @@
+use std::fmt;
+
+use super::IntegerLiteral;
+
+/// An arbitrary-precision integer for literals that do not fit in a primitive
+#[derive(Default, Debug, Clone, PartialEq, Eq, Hash)]
+pub struct BigInt {
+    negative: bool,
+    /// The magnitude in little-endian order without trailing zero limbs
+    limbs: Vec<u64>,
+}
+
+impl BigInt {
+    #[inline(always)]
+    pub const fn is_negative(&self) -> bool {
+        self.negative
+    }
+
+    #[inline(always)]
+    pub fn is_zero(&self) -> bool {
+        self.limbs.is_empty()
+    }
+
+    /// The magnitude in little-endian order
+    #[inline(always)]
+    pub fn limbs(&self) -> &[u64] {
+        &self.limbs
+    }
+
+    pub fn to_u128(&self) -> Option<u128> {
+        if self.negative {
+            return None;
+        }
+
+        match self.limbs[..] {
+            [] => Some(0),
+            [low] => Some(low as u128),
+            [low, high] => Some((high as u128) << 64 | low as u128),
+            _ => None,
+        }
+    }
+
+    pub fn to_i128(&self) -> Option<i128> {
+        let magnitude = match self.limbs[..] {
+            [] => 0,
+            [low] => low as u128,
+            [low, high] => (high as u128) << 64 | low as u128,
+            _ => return None,
+        };
+
+        if self.negative {
+            (magnitude <= i128::MIN.unsigned_abs()).then(|| (magnitude as i128).wrapping_neg())
+        } else {
+            magnitude.try_into().ok()
+        }
+    }
+
+    /// `self = self * mul + add` on the magnitude
+    fn mul_add(&mut self, mul: u64, add: u64) {
+        let mut carry = add as u128;
+
+        for limb in &mut self.limbs {
+            let value = *limb as u128 * mul as u128 + carry;
+            *limb = value as u64;
+            carry = value >> 64;
+        }
+
+        if carry != 0 {
+            self.limbs.push(carry as u64);
+        }
+    }
+
+    /// `self = self / div` on the magnitude, returning the remainder
+    fn div_rem(&mut self, div: u64) -> u64 {
+        let mut rem = 0u128;
+
+        for limb in self.limbs.iter_mut().rev() {
+            let value = rem << 64 | *limb as u128;
+            *limb = (value / div as u128) as u64;
+            rem = value % div as u128;
+        }
+
+        while self.limbs.last() == Some(&0) {
+            self.limbs.pop();
+        }
+
+        rem as u64
+    }
+}
+
+impl From<u128> for BigInt {
+    #[inline(always)]
+    fn from(value: u128) -> Self {
+        let mut big = BigInt {
+            negative: false,
+            limbs: vec![value as u64, (value >> 64) as u64],
+        };
+
+        while big.limbs.last() == Some(&0) {
+            big.limbs.pop();
+        }
+
+        big
+    }
+}
+
+impl From<i128> for BigInt {
+    #[inline(always)]
+    fn from(value: i128) -> Self {
+        let mut big = BigInt::from(value.unsigned_abs());
+        big.negative = value < 0;
+        big
+    }
+}
+
+impl IntegerLiteral for BigInt {
+    fn from_digits(
+        negative: bool,
+        radix: u32,
+        digits: impl IntoIterator<Item = u32>,
+    ) -> Option<Self> {
+        let mut big = BigInt::default();
+
+        for digit in digits {
+            big.mul_add(radix as u64, digit as u64);
+        }
+
+        big.negative = negative && !big.is_zero();
+
+        Some(big)
+    }
+}
+
+impl fmt::Display for BigInt {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        // The largest power of ten that fits in a `u64`
+        const CHUNK: u64 = 10_000_000_000_000_000_000;
+
+        let mut magnitude = self.clone();
+        let mut chunks = Vec::new();
+
+        while !magnitude.is_zero() {
+            chunks.push(magnitude.div_rem(CHUNK));
+        }
+
+        let mut digits = match chunks.pop() {
+            Some(first) => first.to_string(),
+            None => "0".to_string(),
+        };
+
+        for chunk in chunks.iter().rev() {
+            digits.push_str(&format!("{chunk:019}"));
+        }
+
+        f.pad_integral(!self.negative, "", &digits)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn round_trip() {
+        for value in [0, 1, -1, i128::MAX, i128::MIN, 10_000_000_000_000_000_000] {
+            let big = BigInt::from(value);
+
+            assert_eq!(big.to_string(), value.to_string());
+            assert_eq!(big.to_i128(), Some(value));
+        }
+
+        assert_eq!(BigInt::from(u128::MAX).to_u128(), Some(u128::MAX));
+        assert_eq!(BigInt::from(-1i128).to_u128(), None);
+    }
+}
@@ -0,0 +1,451 @@
+use std::borrow::Cow;
+
+use token_precedence::span::{Span, Spanned};
+
+use crate::{
+    input::{Input, InputExt},
+    parse::{IsParse, ParseError, RichError},
+};
+
+/// Parses a string quoted by any char of `quotes`, closed by the same char
+///
+/// Backslash escapes are `\n`, `\r`, `\t`, `\0`, `\\`, `\'`, `\"`,
+/// `\x..` (up to `7f`) and `\u{...}` (1 to 6 hex digits).
+///
+/// # Note
+/// The output is borrowed from the input if the string has no escapes and the input allows it,
+/// see `Input::consume_str`.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub struct QuotedString<'q> {
+    pub quotes: &'q str,
+    /// Enables backslash escapes
+    pub escapes: bool,
+    /// Enables escaping a quote by doubling it, such as `'it''s'`
+    pub doubled_quotes: bool,
+    /// Enables raw strings without escapes such as `r"..."` or `r#"..."#`
+    pub raw: bool,
+}
+
+impl<'q> QuotedString<'q> {
+    #[inline(always)]
+    pub const fn new(quotes: &'q str) -> Self {
+        Self {
+            quotes,
+            escapes: true,
+            doubled_quotes: false,
+            raw: false,
+        }
+    }
+
+    #[inline(always)]
+    pub const fn without_escapes(mut self) -> Self {
+        self.escapes = false;
+        self
+    }
+
+    #[inline(always)]
+    pub const fn with_doubled_quotes(mut self) -> Self {
+        self.doubled_quotes = true;
+        self
+    }
+
+    #[inline(always)]
+    pub const fn with_raw(mut self) -> Self {
+        self.raw = true;
+        self
+    }
+}
+
+impl Default for QuotedString<'_> {
+    #[inline(always)]
+    fn default() -> Self {
+        Self::new("\"")
+    }
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub enum StringErrorKind {
+    /// No opening quote was found
+    Expected,
+    /// The input ended before the closing quote
+    Unterminated,
+    /// The escape at `span` is not valid
+    InvalidEscape,
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub struct StringError {
+    pub kind: StringErrorKind,
+    pub span: Span,
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+enum State {
+    Start,
+    /// After `r` and `fences` `#`s
+    RawFences,
+    Content,
+    Escaped,
+    /// After a closing quote and `hashes` `#`s of a raw string
+    RawClosing {
+        hashes: usize,
+    },
+    /// After a closing quote that may start a doubled quote
+    Closing,
+    Done,
+}
+
+/// Finds the end of a string literal one char at a time
+struct Scanner<'q> {
+    config: QuotedString<'q>,
+    state: State,
+    quote: char,
+    fences: usize,
+    raw: bool,
+    has_escapes: bool,
+}
+
+impl<'q> Scanner<'q> {
+    #[inline(always)]
+    const fn new(config: QuotedString<'q>) -> Self {
+        Scanner {
+            config,
+            state: State::Start,
+            quote: '\0',
+            fences: 0,
+            raw: false,
+            has_escapes: false,
+        }
+    }
+
+    /// Returns true when `c` is past the end of the literal
+    fn stop(&mut self, c: char) -> bool {
+        self.state = match self.state {
+            State::Start if self.config.raw && c == 'r' => {
+                self.raw = true;
+                State::RawFences
+            }
+            State::Start | State::RawFences if self.config.quotes.contains(c) => {
+                self.quote = c;
+                State::Content
+            }
+            State::RawFences if c == '#' => {
+                self.fences += 1;
+                State::RawFences
+            }
+            State::Start | State::RawFences => return true,
+            State::Content if self.raw && c == self.quote => match self.fences {
+                0 => State::Done,
+                _ => State::RawClosing { hashes: 0 },
+            },
+            State::Content if c == self.quote => State::Closing,
+            State::Content if !self.raw && self.config.escapes && c == '\\' => {
+                self.has_escapes = true;
+                State::Escaped
+            }
+            State::Content | State::Escaped => State::Content,
+            State::RawClosing { hashes } if c == '#' => match hashes + 1 {
+                hashes if hashes == self.fences => State::Done,
+                hashes => State::RawClosing { hashes },
+            },
+            State::RawClosing { .. } if c == self.quote => State::RawClosing { hashes: 0 },
+            State::RawClosing { .. } => State::Content,
+            State::Closing if self.config.doubled_quotes && c == self.quote => {
+                self.has_escapes = true;
+                State::Content
+            }
+            State::Closing | State::Done => return true,
+        };
+
+        false
+    }
+
+    /// The lengths of the opening and closing delimiters
+    #[inline(always)]
+    fn delimiters(&self) -> (usize, usize) {
+        let quote = self.quote.len_utf8();
+
+        if self.raw {
+            (1 + self.fences + quote, quote + self.fences)
+        } else {
+            (quote, quote)
+        }
+    }
+}
+
+impl<'a, 'q> IsParse<'a> for QuotedString<'q> {
+    type Output = Spanned<Cow<'a, str>>;
+    type Error = StringError;
+
+    fn __parse<I: ?Sized + Input>(
+        self,
+        input: &'a mut I,
+    ) -> Result<Self::Output, ParseError<Self::Error>> {
+        let mut scanner = Scanner::new(self);
+
+        let entry = input.read_until_entry(16, |c| scanner.stop(c))?;
+
+        let index = entry.input().index();
+        let span = entry.span();
+
+        match scanner.state {
+            State::Closing | State::Done => {}
+            State::Start | State::RawFences => {
+                return Err(ParseError::Error(StringError {
+                    kind: StringErrorKind::Expected,
+                    span: Span::new(index, index),
+                }));
+            }
+            _ => {
+                return Err(ParseError::Error(StringError {
+                    kind: StringErrorKind::Unterminated,
+                    span,
+                }));
+            }
+        }
+
+        let (open, close) = scanner.delimiters();
+        let content = open..entry.get().len() - close;
+
+        if !scanner.has_escapes {
+            let output = match entry.consume_str() {
+                Cow::Borrowed(literal) => Cow::Borrowed(&literal[content]),
+                Cow::Owned(mut literal) => {
+                    literal.truncate(content.end);
+                    literal.drain(..content.start);
+                    Cow::Owned(literal)
+                }
+            };
+
+            return Ok(span.over(output));
+        }
+
+        let output = unescape(&entry.get()[content], scanner.quote, index + open)
+            .map_err(ParseError::Error)?;
+
+        entry.consume();
+        Ok(span.over(Cow::Owned(output)))
+    }
+}
+
+/// Decodes escapes in `content`, which starts at the input index `index`
+fn unescape(content: &str, quote: char, index: usize) -> Result<String, StringError> {
+    let mut output = String::with_capacity(content.len());
+    let mut chars = content.char_indices();
+
+    while let Some((start, c)) = chars.next() {
+        if c == quote {
+            // A doubled quote, as the scanner only lets a quote through when doubled
+            chars.next();
+            output.push(quote);
+            continue;
+        }
+
+        if c != '\\' {
+            output.push(c);
+            continue;
+        }
+
+        let invalid = |end: usize| StringError {
+            kind: StringErrorKind::InvalidEscape,
+            span: Span::new(index + start, index + end),
+        };
+
+        let Some((i, escape)) = chars.next() else {
+            return Err(invalid(content.len()));
+        };
+
+        let end = i + escape.len_utf8();
+
+        output.push(match escape {
+            'n' => '\n',
+            'r' => '\r',
+            't' => '\t',
+            '0' => '\0',
+            '\\' | '\'' | '"' => escape,
+            'x' => {
+                let hex = content.get(end..end + 2).ok_or(invalid(content.len()))?;
+
+                // `from_str_radix` also accepts a leading `+`
+                let code = Some(hex)
+                    .filter(|hex| is_hex(hex))
+                    .and_then(|hex| u8::from_str_radix(hex, 16).ok())
+                    .filter(u8::is_ascii)
+                    .ok_or(invalid(end + 2))?;
+
+                chars.nth(1);
+
+                code as char
+            }
+            'u' => {
+                // The escape ends at a `}` within 6 digits, or else after the digits of its group
+                let group = content[end..].strip_prefix('{');
+                let close = group
+                    .and_then(|group| group.bytes().take(7).position(|b| b == b'}'))
+                    .map(|close| end + 1 + close);
+
+                let escape_end = match (group, close) {
+                    (_, Some(close)) => close + 1,
+                    (Some(group), None) => {
+                        let digits = group.bytes().take(6).take_while(u8::is_ascii_hexdigit);
+                        end + 1 + digits.count()
+                    }
+                    (None, None) => end,
+                };
+
+                let code = close
+                    .map(|close| &content[end + 1..close])
+                    .filter(|hex| !hex.is_empty() && is_hex(hex))
+                    .and_then(|hex| u32::from_str_radix(hex, 16).ok())
+                    .and_then(char::from_u32)
+                    .ok_or(invalid(escape_end))?;
+
+                chars.nth(close.unwrap() - end);
+
+                code
+            }
+            _ => return Err(invalid(end)),
+        });
+    }
+
+    Ok(output)
+}
+
+#[inline(always)]
+fn is_hex(digits: &str) -> bool {
+    digits.bytes().all(|b| b.is_ascii_hexdigit())
+}
+
+impl From<StringError> for RichError {
+    #[inline(always)]
+    fn from(value: StringError) -> Self {
+        RichError::new(value.span).expecting(match value.kind {
+            StringErrorKind::Expected => "string",
+            StringErrorKind::Unterminated => "closing quote",
+            StringErrorKind::InvalidEscape => "valid escape",
+        })
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::{
+        input::{ReadError, ReaderInput, StrView},
+        parse::Parse,
+    };
+
+    use super::*;
+
+    fn parse(config: QuotedString, literal: &str) -> Result<Cow<'static, str>, StringError> {
+        config
+            .parse(&mut StrView::new(literal))
+            .map(|output| Cow::Owned(output.inner.into_owned()))
+            .map_err(|err| match err {
+                ParseError::Error(err) => err,
+                ParseError::ReadError(err) => panic!("{err:?}"),
+            })
+    }
+
+    #[test]
+    fn borrowed() {
+        let mut input = StrView::new("\"hello\" 'world'");
+
+        let output = QuotedString::default().parse(&mut input).unwrap();
+        assert!(matches!(output.inner, Cow::Borrowed("hello")));
+        assert_eq!(output.span, Span::new(0, 7));
+
+        let mut input = ReaderInput::<_, 16>::new("'world'".as_bytes());
+        let output = QuotedString::new("\"'").parse(&mut input).unwrap();
+        assert!(matches!(output.inner, Cow::Borrowed("world")));
+    }
+
+    #[test]
+    fn escapes() {
+        assert_eq!(
+            parse(QuotedString::default(), r#""a\n\t\\\"\x41\u{1F600}""#),
+            Ok("a\n\t\\\"A😀".into())
+        );
+        assert_eq!(
+            parse(QuotedString::new("'").with_doubled_quotes(), "'it''s' x"),
+            Ok("it's".into())
+        );
+        assert_eq!(
+            parse(QuotedString::default().without_escapes(), r#""a\" b"#),
+            Ok(r"a\".into())
+        );
+    }
+
+    #[test]
+    fn raw() {
+        let config = QuotedString::default().with_raw();
+
+        assert_eq!(parse(config, r#"r"a\n""#), Ok(r"a\n".into()));
+        assert_eq!(
+            parse(config, r###"r##"a "# b"##"###),
+            Ok(r##"a "# b"##.into())
+        );
+        assert_eq!(
+            parse(config, r##"r#"a""##).map_err(|err| err.kind),
+            Err(StringErrorKind::Unterminated)
+        );
+    }
+
+    #[test]
+    fn errors() {
+        let error = |kind, start, end| {
+            Err(StringError {
+                kind,
+                span: Span::new(start, end),
+            })
+        };
+
+        let config = QuotedString::default();
+
+        assert_eq!(
+            parse(config, r#""ab\qc""#),
+            error(StringErrorKind::InvalidEscape, 3, 5)
+        );
+        assert_eq!(
+            parse(config, r#""\x80""#),
+            error(StringErrorKind::InvalidEscape, 1, 5)
+        );
+        assert_eq!(
+            parse(config, r#""\u{110000}""#),
+            error(StringErrorKind::InvalidEscape, 1, 11)
+        );
+        assert_eq!(
+            parse(config, r#""\x+7""#),
+            error(StringErrorKind::InvalidEscape, 1, 5)
+        );
+        assert_eq!(
+            parse(config, r#""\u{+41}""#),
+            error(StringErrorKind::InvalidEscape, 1, 8)
+        );
+        assert_eq!(
+            parse(config, r#""\u12""#),
+            error(StringErrorKind::InvalidEscape, 1, 3)
+        );
+        assert_eq!(
+            parse(config, r#""\uAB and {x}""#),
+            error(StringErrorKind::InvalidEscape, 1, 3)
+        );
+        assert_eq!(
+            parse(config, r#""\u{1234567}""#),
+            error(StringErrorKind::InvalidEscape, 1, 10)
+        );
+        assert_eq!(
+            parse(config, r#""\u{} x""#),
+            error(StringErrorKind::InvalidEscape, 1, 5)
+        );
+        assert_eq!(
+            parse(config, r#""abc"#),
+            error(StringErrorKind::Unterminated, 0, 4)
+        );
+        assert_eq!(parse(config, "abc"), error(StringErrorKind::Expected, 0, 0));
+
+        assert_eq!(
+            config.parse(&mut StrView::new("")),
+            Err(ParseError::ReadError(ReadError::EOF))
+        );
+    }
+}
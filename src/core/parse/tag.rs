@@ -0,0 +1,180 @@
+use token_precedence::span::Span;
+
+use crate::{
+    input::{Entry, Input},
+    parse::{IsParse, ParseError, RichError},
+};
+
+use super::{is_xid_continue, read_ahead};
+
+/// Matches the literal `.0` exactly
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub struct Tag<'t>(pub &'t str);
+
+/// Matches the literal `.0` ignoring ASCII case
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub struct TagNoCase<'t>(pub &'t str);
+
+/// Matches the literal `.0` exactly when it is not followed by an identifier char
+///
+/// # Note
+/// Identifier chars are XID_Continue chars, see `is_xid_continue`,
+/// so `KeywordTag("if")` does not match `iffy` or `if` followed by a combining mark.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub struct KeywordTag<'t>(pub &'t str);
+
+/// The literal `tag` was not found at `span`
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub struct ExpectedTag<'t> {
+    pub tag: &'t str,
+    pub span: Span,
+}
+
+impl<'a, 't> IsParse<'a> for Tag<'t> {
+    type Output = Span;
+    type Error = ExpectedTag<'t>;
+
+    #[inline(always)]
+    fn __parse<I: ?Sized + Input>(
+        self,
+        input: &'a mut I,
+    ) -> Result<Self::Output, ParseError<Self::Error>> {
+        let matches = read_ahead(input, self.0.len())?.starts_with(self.0);
+
+        consume_tag(input, self.0, matches)
+    }
+}
+
+impl<'a, 't> IsParse<'a> for TagNoCase<'t> {
+    type Output = Span;
+    type Error = ExpectedTag<'t>;
+
+    #[inline(always)]
+    fn __parse<I: ?Sized + Input>(
+        self,
+        input: &'a mut I,
+    ) -> Result<Self::Output, ParseError<Self::Error>> {
+        let matches = read_ahead(input, self.0.len())?
+            .as_bytes()
+            .get(..self.0.len())
+            .is_some_and(|read| read.eq_ignore_ascii_case(self.0.as_bytes()));
+
+        consume_tag(input, self.0, matches)
+    }
+}
+
+impl<'a, 't> IsParse<'a> for KeywordTag<'t> {
+    type Output = Span;
+    type Error = ExpectedTag<'t>;
+
+    #[inline(always)]
+    fn __parse<I: ?Sized + Input>(
+        self,
+        input: &'a mut I,
+    ) -> Result<Self::Output, ParseError<Self::Error>> {
+        let matches = read_ahead(input, self.0.len() + 4)?
+            .strip_prefix(self.0)
+            .is_some_and(|rest| !rest.chars().next().is_some_and(is_xid_continue));
+
+        consume_tag(input, self.0, matches)
+    }
+}
+
+#[inline(always)]
+fn consume_tag<'t, I: ?Sized + Input>(
+    input: &mut I,
+    tag: &'t str,
+    matches: bool,
+) -> Result<Span, ParseError<ExpectedTag<'t>>> {
+    if !matches {
+        return Err(ParseError::Error(ExpectedTag {
+            tag,
+            span: Span::new(input.index(), input.index()),
+        }));
+    }
+
+    // The buffered data starts with `tag` (up to ASCII case) so `tag.len()` is a char boundary
+    let entry = unsafe { Entry::new(input, tag.len()) };
+    let span = entry.span();
+    entry.consume();
+
+    Ok(span)
+}
+
+impl From<ExpectedTag<'_>> for RichError {
+    #[inline(always)]
+    fn from(value: ExpectedTag<'_>) -> Self {
+        RichError::new(value.span).expecting(format!("`{}`", value.tag))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::{
+        input::{ReadError, ReaderInput, StrView},
+        parse::Parse,
+    };
+
+    use super::*;
+
+    #[test]
+    fn tags() {
+        let mut input = StrView::new("letlet");
+
+        assert_eq!(Tag("let").parse(&mut input), Ok(Span::new(0, 3)));
+        assert_eq!(
+            Tag("lets").parse(&mut input),
+            Err(ParseError::Error(ExpectedTag {
+                tag: "lets",
+                span: Span::new(3, 3)
+            }))
+        );
+        assert_eq!(Tag("let").parse(&mut input), Ok(Span::new(3, 6)));
+        assert_eq!(
+            Tag("let").parse(&mut input),
+            Err(ParseError::ReadError(ReadError::EOF))
+        );
+    }
+
+    #[test]
+    fn no_case() {
+        let mut input = ReaderInput::<_, 16>::new("SeLeCt é".as_bytes());
+
+        assert_eq!(TagNoCase("select").parse(&mut input), Ok(Span::new(0, 6)));
+        assert!(TagNoCase(" É").parse(&mut input).is_err());
+        assert_eq!(TagNoCase(" é").parse(&mut input), Ok(Span::new(6, 9)));
+    }
+
+    #[test]
+    fn keywords() {
+        assert_eq!(
+            KeywordTag("if").parse(&mut StrView::new("if(")),
+            Ok(Span::new(0, 2))
+        );
+        assert_eq!(
+            KeywordTag("if").parse(&mut StrView::new("if")),
+            Ok(Span::new(0, 2))
+        );
+
+        let mut input = StrView::new("iffy");
+        assert_eq!(
+            KeywordTag("if").parse(&mut input),
+            Err(ParseError::Error(ExpectedTag {
+                tag: "if",
+                span: Span::new(0, 0)
+            }))
+        );
+        assert_eq!(input.read(), "iffy");
+
+        assert!(KeywordTag("if").parse(&mut StrView::new("if_")).is_err());
+        assert!(
+            KeywordTag("if")
+                .parse(&mut StrView::new("if\u{301}"))
+                .is_err()
+        );
+        assert_eq!(
+            KeywordTag("if").parse(&mut StrView::new("if\u{3000}")),
+            Ok(Span::new(0, 2))
+        );
+    }
+}
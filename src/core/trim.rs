@@ -1,8 +1,13 @@
+use token_precedence::span::Span;
+
 use crate::{
-    input::{self, Input, InputExt},
-    trim::Trim,
+    input::{self, Input, InputExt, ReadError},
+    parse::{ParseError, RichError},
+    trim::{Trim, TryTrim},
 };
 
+use super::parse::read_ahead;
+
 #[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
 pub struct TrimUntil<F: Fn(char) -> bool> {
     func: F,
@@ -30,3 +35,194 @@ impl Trim for TrimWhitespace {
         input.consume_until(8, |c| !c.is_whitespace())
     }
 }
+
+/// Skips whitespace and comments
+///
+/// # Note
+/// Markers are matched in order, so a marker that is a prefix of another should come after it.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub struct TrimComments<'c> {
+    /// Markers of comments that run to the end of the line, such as `//`
+    pub line: &'c [&'c str],
+    /// Opening and closing delimiters of block comments, such as `("/*", "*/")`
+    pub block: &'c [(&'c str, &'c str)],
+    /// Allows block comments to nest
+    pub nested: bool,
+}
+
+impl<'c> TrimComments<'c> {
+    #[inline(always)]
+    pub const fn new(line: &'c [&'c str], block: &'c [(&'c str, &'c str)]) -> Self {
+        Self {
+            line,
+            block,
+            nested: false,
+        }
+    }
+
+    #[inline(always)]
+    pub const fn nested(mut self) -> Self {
+        self.nested = true;
+        self
+    }
+
+    /// Skips a block comment, the input starting with `open`
+    fn skip_block<E: From<UnterminatedComment>, I: ?Sized + Input>(
+        self,
+        input: &mut I,
+        open: &str,
+        close: &str,
+    ) -> Result<(), ParseError<E>> {
+        let start = input.index();
+        let unterminated = || {
+            ParseError::Error(E::from(UnterminatedComment {
+                span: Span::new(start, start + open.len()),
+            }))
+        };
+
+        let close_first = close.chars().next();
+        let open_first = open.chars().next().filter(|_| self.nested);
+
+        // The buffered data starts with `open`
+        unsafe { input.consume(open.len()) };
+
+        let mut depth = 1usize;
+
+        loop {
+            let read = match input
+                .consume_until(8, |c| Some(c) == close_first || Some(c) == open_first)
+                .and_then(|()| read_ahead(input, open.len().max(close.len())))
+            {
+                Ok(read) => read,
+                Err(ReadError::EOF) => return Err(unterminated()),
+                Err(err) => return Err(ParseError::ReadError(err)),
+            };
+
+            let len = if read.starts_with(close) {
+                depth -= 1;
+                close.len()
+            } else if self.nested && read.starts_with(open) {
+                depth += 1;
+                open.len()
+            } else {
+                read.chars().next().map_or(0, char::len_utf8)
+            };
+
+            // `len` is the length of a delimiter or char at the start of the buffered data
+            unsafe { input.consume(len) };
+
+            if depth == 0 {
+                return Ok(());
+            }
+        }
+    }
+}
+
+/// A block comment opened at `span` is not closed before the end of input
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub struct UnterminatedComment {
+    pub span: Span,
+}
+
+impl<E: From<UnterminatedComment>> TryTrim<E> for TrimComments<'_> {
+    fn try_trim<I: ?Sized + Input>(self, input: &mut I) -> Result<(), ParseError<E>> {
+        let max_len = self
+            .line
+            .iter()
+            .chain(self.block.iter().map(|(open, _)| open))
+            .map(|marker| marker.len())
+            .max()
+            .unwrap_or(0);
+
+        loop {
+            input.consume_until(8, |c| !c.is_whitespace())?;
+
+            let read = read_ahead(input, max_len)?;
+
+            if let Some(marker) = self.line.iter().find(|marker| read.starts_with(**marker)) {
+                // The buffered data starts with `marker`
+                unsafe { input.consume(marker.len()) };
+                input.consume_until(8, |c| c == '\n')?;
+            } else if let Some(&(open, close)) =
+                self.block.iter().find(|(open, _)| read.starts_with(*open))
+            {
+                self.skip_block(input, open, close)?;
+            } else {
+                return Ok(());
+            }
+        }
+    }
+}
+
+impl From<UnterminatedComment> for RichError {
+    #[inline(always)]
+    fn from(value: UnterminatedComment) -> Self {
+        RichError::new(value.span).expecting("end of comment")
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::input::{ReaderInput, StrView};
+
+    use super::*;
+
+    const C: TrimComments = TrimComments::new(&["//", "#"], &[("/*", "*/")]);
+
+    fn trim<I: Input>(
+        trim: TrimComments,
+        input: &mut I,
+    ) -> Result<(), ParseError<UnterminatedComment>> {
+        trim.try_trim(input)
+    }
+
+    #[test]
+    fn comments() {
+        let mut input = StrView::new("  // a\n# b\n /* c */ /* d\n */x // e");
+
+        assert_eq!(trim(C, &mut input), Ok(()));
+        assert_eq!(input.read(), "x // e");
+
+        unsafe { input.consume(1) };
+        assert_eq!(
+            trim(C, &mut input),
+            Err(ParseError::ReadError(ReadError::EOF))
+        );
+
+        let mut input = ReaderInput::<_, 16>::new("/* a ** b ****/ -- c\n/**/y".as_bytes());
+        let comments = TrimComments::new(&["--"], &[("/*", "*/")]);
+
+        assert_eq!(trim(comments, &mut input), Ok(()));
+        assert_eq!(&input.read()[..1], "y");
+    }
+
+    #[test]
+    fn nested() {
+        let mut input = StrView::new("/* a /* b */ c */x");
+        assert_eq!(trim(C.nested(), &mut input), Ok(()));
+        assert_eq!(input.read(), "x");
+
+        let mut input = StrView::new("/* a /* b */ c */x");
+        assert_eq!(trim(C, &mut input), Ok(()));
+        assert_eq!(input.read(), "c */x");
+    }
+
+    #[test]
+    fn unterminated() {
+        let unterminated = |start| {
+            Err(ParseError::Error(UnterminatedComment {
+                span: Span::new(start, start + 2),
+            }))
+        };
+
+        assert_eq!(trim(C, &mut StrView::new(" /* a")), unterminated(1));
+        assert_eq!(
+            trim(C.nested(), &mut StrView::new("/* /* */ ")),
+            unterminated(0)
+        );
+        assert_eq!(
+            trim(C, &mut ReaderInput::<_, 16>::new("  /* abc *".as_bytes())),
+            unterminated(2)
+        );
+    }
+}
@@ -1,22 +1,28 @@
-use std::result;
+use std::{borrow::Cow, result};
 
 mod default;
 mod entry;
+mod lookahead;
 
 mod reader_input;
 mod str_view;
 
 pub use entry::{CharEntry, Entry, InputExt};
+pub use lookahead::Lookahead;
 
 pub use reader_input::ReaderInput;
 pub use str_view::StrView;
 
 const EOF_INDEX: usize = usize::MAX;
 
+/// # Note
+/// More errors may be added without a major version change, so matches need a wildcard arm.
 #[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+#[non_exhaustive]
 pub enum ReadError {
     EOF,
     InvalidUtf8(InvalidUtf8),
+    TokenTooLong(TokenTooLong),
 }
 
 #[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
@@ -24,6 +30,14 @@ pub struct InvalidUtf8 {
     pub index: usize,
 }
 
+/// More data was requested at once than the buffer of the input can hold,
+/// such as a token longer than the buffer of a `ReaderInput`
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub struct TokenTooLong {
+    /// Where the requested data starts
+    pub index: usize,
+}
+
 pub type Result<T> = result::Result<T, ReadError>;
 
 impl From<InvalidUtf8> for ReadError {
@@ -33,6 +47,13 @@ impl From<InvalidUtf8> for ReadError {
     }
 }
 
+impl From<TokenTooLong> for ReadError {
+    #[inline(always)]
+    fn from(value: TokenTooLong) -> Self {
+        Self::TokenTooLong(value)
+    }
+}
+
 /// # Input
 /// It is recomended to
 pub trait Input {
@@ -43,6 +64,8 @@ pub trait Input {
     fn index(&self) -> usize;
     fn read(&self) -> &str;
     /// Will buffer at least `n - 3` bytes of data depending on char boundaries.
+    ///
+    /// Returns `ReadError::TokenTooLong` if `n` bytes cannot fit in the buffer.
     fn buffer_at_least(&mut self, n: usize) -> Result<()>;
     fn set_eof(&mut self);
     fn is_eof(&self) -> bool;
@@ -54,6 +77,16 @@ pub trait Input {
     /// Try to use the entry system within `InputExt` instead to avoid unsafe code.
     unsafe fn consume(&mut self, n: usize);
 
+    /// Consumes `n` bytes and returns them,
+    /// borrowed if the input keeps consumed data in place until it is next buffered
+    ///
+    /// # Safety
+    /// The same as `consume`.
+    #[inline(always)]
+    unsafe fn consume_str(&mut self, n: usize) -> Cow<'_, str> {
+        unsafe { default::default_consume_str(self, n) }
+    }
+
     /// Will read at least `n - 3` bytes of data depending on char boundaries
     #[inline(always)]
     fn read_at_least(&mut self, n: usize) -> Result<&str> {
@@ -74,6 +107,13 @@ pub trait Input {
 pub mod tests {
     use std::{cmp, io, ptr};
 
+    use token_precedence::span::Span;
+
+    use crate::{
+        core::parse::Identifier,
+        parse::{Parse, ParseError},
+    };
+
     use super::*;
 
     struct ReadOneAtATime<'a> {
@@ -133,6 +173,36 @@ pub mod tests {
         assert_eq!(input.read_at_least(1), Ok("world!"));
     }
 
+    #[test]
+    fn longer_than_buffer() {
+        let mut input = ReaderInput::<_, 12>::new("hello world!".as_bytes());
+
+        assert_eq!(
+            input.read_at_least(13),
+            Err(ReadError::TokenTooLong(TokenTooLong { index: 0 }))
+        );
+        assert_eq!(input.read_at_least(12), Ok("hello world!"));
+    }
+
+    #[test]
+    fn identifier_longer_than_buffer() {
+        let long = "x".repeat(100);
+        let parse = |s: &str| {
+            Identifier::new()
+                .parse(&mut ReaderInput::<_, 64>::new(s.as_bytes()))
+                .map(|output| output.span)
+        };
+
+        assert_eq!(
+            parse(&format!("{long} y")),
+            Err(ParseError::ReadError(ReadError::TokenTooLong(
+                TokenTooLong { index: 0 }
+            )))
+        );
+        assert_eq!(parse(&long[..50]), Ok(Span::new(0, 50)));
+        assert_eq!(parse(&format!("{} y", &long[..59])), Ok(Span::new(0, 59)));
+    }
+
     #[test]
     fn deal_with_utf8() {
         let data = ReadOneAtATime::new("party 🎉 🎉!".as_bytes());
@@ -1,3 +1,5 @@
+use std::borrow::Cow;
+
 use super::{Input, ReadError, Result};
 
 #[inline(always)]
@@ -8,16 +10,17 @@ pub(super) fn default_read_at_least<I: ?Sized + Input>(input: &mut I, n: usize)
 pub(super) fn default_consume_until<I: ?Sized + Input>(
     input: &mut I,
     chunk_size: usize,
-    func: impl Fn(char) -> bool,
+    mut func: impl FnMut(char) -> bool,
 ) -> Result<()> {
     if input.is_eof() {
         return Err(ReadError::EOF);
     }
 
     loop {
-        if let Result::Err(ReadError::InvalidUtf8(err)) = input.buffer_at_least(chunk_size) {
-            return Err(ReadError::InvalidUtf8(err));
-        };
+        match input.buffer_at_least(chunk_size) {
+            Ok(()) | Err(ReadError::EOF) => {}
+            Err(err) => return Err(err),
+        }
 
         if input.is_eof() {
             return Err(ReadError::EOF);
@@ -39,43 +42,69 @@ pub(super) fn default_consume_until<I: ?Sized + Input>(
 pub(super) fn default_read_until<I: ?Sized + Input>(
     input: &mut I,
     chunk_size: usize,
-    func: impl Fn(char) -> bool,
+    mut func: impl FnMut(char) -> bool,
 ) -> Result<&str> {
     if input.is_eof() {
         return Err(ReadError::EOF);
     }
 
-    let mut first_loop = true;
     let mut count = 0;
 
-    loop {
-        if let Result::Err(ReadError::InvalidUtf8(err)) = input.buffer_at_least(chunk_size) {
-            return Err(ReadError::InvalidUtf8(err));
+    let len = 'search: loop {
+        let result = match input.buffer_at_least(count + chunk_size) {
+            // A whole chunk may not fit after a long match, a single char still might
+            Err(ReadError::TokenTooLong(_)) if chunk_size > 4 => input.buffer_at_least(count + 4),
+            result => result,
+        };
+
+        // An EOF here only means there is less data than requested
+        let reached_end = match result {
+            Ok(()) => false,
+            Err(ReadError::EOF) => true,
+            Err(err) => return Err(err),
         };
 
-        if input.is_eof() && first_loop {
+        if input.is_eof() {
             return Err(ReadError::EOF);
         }
 
-        first_loop = false;
-
         let read = input.read();
 
-        for (i, c) in read.char_indices() {
+        for (i, c) in read[count..].char_indices() {
             if func(c) {
-                let s = unsafe { str::from_utf8_unchecked(input.get_unchecked(count + i)) };
-
-                return Ok(s);
+                break 'search count + i;
             }
         }
 
-        count += read.len();
-    }
+        count = read.len();
+
+        if reached_end {
+            break count;
+        }
+    };
+
+    Ok(unsafe { str::from_utf8_unchecked(input.get_unchecked(len)) })
 }
 
 #[inline(always)]
 pub(super) fn default_peek<I: ?Sized + Input>(input: &mut I) -> Result<char> {
-    input
-        .read_at_least(4)
-        .map(|s| unsafe { s.chars().next().unwrap_unchecked() })
+    match input.buffer_at_least(4) {
+        Ok(()) => {}
+        // Fewer than 4 bytes may be left before the end of input
+        Err(ReadError::EOF) if !input.is_eof() => {}
+        Err(err) => return Err(err),
+    }
+
+    input.read().chars().next().ok_or(ReadError::EOF)
+}
+
+#[inline(always)]
+pub(super) unsafe fn default_consume_str<I: ?Sized + Input>(
+    input: &mut I,
+    n: usize,
+) -> Cow<'_, str> {
+    let owned = unsafe { str::from_utf8_unchecked(input.get_unchecked(n)) }.to_owned();
+    unsafe { input.consume(n) };
+
+    Cow::Owned(owned)
 }
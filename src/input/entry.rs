@@ -1,3 +1,5 @@
+use std::borrow::Cow;
+
 use token_precedence::span::Span;
 
 use super::default;
@@ -29,14 +31,14 @@ pub trait InputExt: Input {
     }
 
     #[inline(always)]
-    fn consume_until(&mut self, chunk_size: usize, func: impl Fn(char) -> bool) -> Result<()> {
+    fn consume_until(&mut self, chunk_size: usize, func: impl FnMut(char) -> bool) -> Result<()> {
         default::default_consume_until(self, chunk_size, func)
     }
 
     /// # Note
     /// Use `InputExt::read_until_entry` instead if you plan to consume the whole string slice
     #[inline(always)]
-    fn read_until(&mut self, chunk_size: usize, func: impl Fn(char) -> bool) -> Result<&str> {
+    fn read_until(&mut self, chunk_size: usize, func: impl FnMut(char) -> bool) -> Result<&str> {
         default::default_read_until(self, chunk_size, func)
     }
 
@@ -44,7 +46,7 @@ pub trait InputExt: Input {
     fn read_until_entry<'a>(
         &'a mut self,
         chunk_size: usize,
-        func: impl Fn(char) -> bool,
+        func: impl FnMut(char) -> bool,
     ) -> Result<Entry<'a, Self>> {
         let len = self.read_until(chunk_size, func)?.len();
 
@@ -103,6 +105,14 @@ pub struct Entry<'a, I: ?Sized + Input = dyn Input + 'a> {
 }
 
 impl<'a, I: ?Sized + Input> Entry<'a, I> {
+    /// # Safety
+    /// `size` must not exceed the length of currently buffered data
+    /// and must offset the input to an existing char boundary.
+    #[inline(always)]
+    pub(crate) const unsafe fn new(input: &'a mut I, size: usize) -> Self {
+        Entry { input, size }
+    }
+
     #[inline(always)]
     pub const fn input(&self) -> &I {
         self.input
@@ -118,6 +128,13 @@ impl<'a, I: ?Sized + Input> Entry<'a, I> {
         unsafe { self.input.consume(self.size) };
     }
 
+    /// Consumes the entry and returns its string slice,
+    /// borrowed from the input when the input allows it
+    #[inline(always)]
+    pub fn consume_str(self) -> Cow<'a, str> {
+        unsafe { self.input.consume_str(self.size) }
+    }
+
     #[inline(always)]
     pub const fn discard(self) {}
 
@@ -0,0 +1,139 @@
+use std::borrow::Cow;
+
+use super::{EOF_INDEX, Input, ReadError, Result};
+
+/// Reads ahead of `input` without consuming it,
+/// consuming from a `Lookahead` only advances its own offset
+///
+/// # Note
+/// Reading ahead requires the buffered data of `input` to hold both the offset and the read,
+/// so a `ReaderInput` cannot look further ahead than its buffer size.
+pub struct Lookahead<'i, I: ?Sized + Input> {
+    input: &'i mut I,
+    offset: usize,
+    eof: bool,
+}
+
+impl<'i, I: ?Sized + Input> Lookahead<'i, I> {
+    #[inline(always)]
+    pub fn new(input: &'i mut I) -> Self {
+        Lookahead {
+            input,
+            offset: 0,
+            eof: false,
+        }
+    }
+
+    /// The number of bytes consumed from the lookahead
+    #[inline(always)]
+    pub const fn offset(&self) -> usize {
+        self.offset
+    }
+}
+
+impl<'i, I: ?Sized + Input> Input for Lookahead<'i, I> {
+    #[inline(always)]
+    unsafe fn get_unchecked(&self, n: usize) -> &[u8] {
+        unsafe {
+            self.input
+                .get_unchecked(self.offset + n)
+                .get_unchecked(self.offset..)
+        }
+    }
+
+    #[inline(always)]
+    fn index(&self) -> usize {
+        if self.is_eof() {
+            EOF_INDEX
+        } else {
+            self.input.index() + self.offset
+        }
+    }
+
+    #[inline(always)]
+    fn read(&self) -> &str {
+        unsafe { self.input.read().get_unchecked(self.offset..) }
+    }
+
+    #[inline(always)]
+    fn buffer_at_least(&mut self, n: usize) -> Result<()> {
+        if self.eof {
+            return Err(ReadError::EOF);
+        }
+
+        let result = self.input.buffer_at_least(self.offset + n);
+
+        if result == Err(ReadError::EOF) && self.read().is_empty() {
+            self.eof = true;
+        }
+
+        result
+    }
+
+    #[inline(always)]
+    fn set_eof(&mut self) {
+        self.eof = true;
+    }
+
+    #[inline(always)]
+    fn is_eof(&self) -> bool {
+        self.eof || self.input.is_eof()
+    }
+
+    #[inline(always)]
+    unsafe fn consume(&mut self, n: usize) {
+        self.offset += n;
+    }
+
+    #[inline(always)]
+    unsafe fn consume_str(&mut self, n: usize) -> Cow<'_, str> {
+        let start = self.offset;
+        self.offset += n;
+
+        Cow::Borrowed(unsafe {
+            str::from_utf8_unchecked(self.input.get_unchecked(self.offset).get_unchecked(start..))
+        })
+    }
+
+    #[inline(always)]
+    fn trait_obj(&mut self) -> &mut dyn Input {
+        self
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::input::{InputExt, ReaderInput, StrView};
+
+    use super::*;
+
+    #[test]
+    fn does_not_consume() {
+        let mut input = StrView::new("ab");
+
+        let mut lookahead = Lookahead::new(&mut input);
+        lookahead.peek_entry().unwrap().consume();
+        assert_eq!(lookahead.index(), 1);
+        assert_eq!(lookahead.peek(), Ok('b'));
+        lookahead.peek_entry().unwrap().consume();
+        assert_eq!(lookahead.peek(), Err(ReadError::EOF));
+        assert!(lookahead.is_eof());
+
+        assert!(!input.is_eof());
+        assert_eq!(input.index(), 0);
+        assert_eq!(input.read(), "ab");
+    }
+
+    #[test]
+    fn reader_input() {
+        let mut input = ReaderInput::<_, 16>::new("hello world".as_bytes());
+
+        let mut lookahead = Lookahead::new(&mut input);
+        assert_eq!(lookahead.read_at_least(5).map(|s| &s[..5]), Ok("hello"));
+        unsafe { lookahead.consume(6) };
+        assert_eq!(lookahead.read_until(4, |c| c == 'd'), Ok("worl"));
+
+        assert_eq!(input.index(), 0);
+        assert_eq!(input.read_at_least(5).map(|s| &s[..5]), Ok("hello"));
+    }
+}
@@ -1,6 +1,6 @@
-use std::{io, ptr};
+use std::{borrow::Cow, io, ptr};
 
-use super::{EOF_INDEX, Input, InvalidUtf8, ReadError, Result};
+use super::{EOF_INDEX, Input, InvalidUtf8, ReadError, Result, TokenTooLong};
 
 const BUFFER_SIZE: usize = 8192;
 
@@ -63,12 +63,14 @@ impl<R: io::Read, const N: usize> Input for ReaderInput<R, N> {
     }
 
     fn buffer_at_least(&mut self, n: usize) -> Result<()> {
-        assert!(n <= N, "buffer overflow");
-
         if self.index == EOF_INDEX {
             return Err(ReadError::EOF);
         }
 
+        if n > N {
+            return Err(ReadError::TokenTooLong(TokenTooLong { index: self.index }));
+        }
+
         // Filling the buffer if needed
         if self.cursor + n > self.filled {
             // Moving the data past the cursor to the start of the buffer
@@ -98,7 +100,8 @@ impl<R: io::Read, const N: usize> Input for ReaderInput<R, N> {
                 // EOF because std::io::read returned 0 bytes
                 if result == 0 {
                     // EOF that we have reached already because of `first_loop`
-                    if first_loop {
+                    // and there is no buffered data left to read
+                    if first_loop && self.cursor == self.filled {
                         self.index = EOF_INDEX;
                         return Err(ReadError::EOF);
                     }
@@ -149,6 +152,20 @@ impl<R: io::Read, const N: usize> Input for ReaderInput<R, N> {
         self.cursor += n;
     }
 
+    /// # Safety
+    /// The same as `consume`
+    ///
+    /// # Note
+    /// Consumed data stays in the buffer until it is next filled.
+    #[inline(always)]
+    unsafe fn consume_str(&mut self, n: usize) -> Cow<'_, str> {
+        unsafe { self.consume(n) };
+
+        Cow::Borrowed(unsafe {
+            str::from_utf8_unchecked(self.buffer.get_unchecked(self.cursor - n..self.cursor))
+        })
+    }
+
     #[inline(always)]
     fn set_eof(&mut self) {
         self.index = EOF_INDEX;
@@ -1,3 +1,5 @@
+use std::borrow::Cow;
+
 use super::{EOF_INDEX, Input, ReadError, Result};
 
 pub struct StrView<'a> {
@@ -59,6 +61,14 @@ impl<'a> Input for StrView<'a> {
         self.index += n;
     }
 
+    #[inline(always)]
+    unsafe fn consume_str(&mut self, n: usize) -> Cow<'_, str> {
+        let consumed = unsafe { self.data.get_unchecked(..n) };
+        unsafe { self.consume(n) };
+
+        Cow::Borrowed(consumed)
+    }
+
     #[inline(always)]
     fn peek(&mut self) -> Result<char> {
         self.data.chars().next().ok_or_else(|| {
@@ -1,23 +1,241 @@
+use std::{
+    cmp, error, fmt,
+    hash::{Hash, Hasher},
+    iter::{Enumerate, FusedIterator},
+    marker::PhantomData,
+    num::NonZeroU32,
+    slice, str,
+    sync::atomic::{AtomicU32, Ordering},
+};
+
+use token_precedence::span::Spanned;
+
+use crate::input::{self, Input};
+
+// gxhash reads past the end of short keys, which Miri rejects, so Miri uses the std hasher
+#[cfg(not(miri))]
 use gxhash::{HashMap, HashMapExt};
+#[cfg(miri)]
+use std::collections::HashMap;
+
+mod arena;
+mod id_map;
+mod normalize;
+mod scope;
+mod typed;
+
+use arena::{Arena, Symbol};
+
+pub use id_map::{Entry, IdMap, IdVec, IdVecIter, OccupiedEntry, VacantEntry};
+pub use normalize::{AsciiCaseFold, Normalize, NormalizingInterner, UnicodeCaseFold};
+pub use scope::ScopedSymbolTable;
+pub use typed::TypedInterner;
+
+/// Identifies the `Interner` an `Id` came from in debug builds,
+/// in release builds this is zero sized and every check is skipped.
+#[derive(Clone, Copy, PartialEq, Eq)]
+struct Brand {
+    #[cfg(debug_assertions)]
+    tag: u32,
+}
+
+impl Brand {
+    /// Ids that were created manually or deserialized,
+    /// these cannot be checked and are accepted by every `Interner`
+    const UNBRANDED: Brand = Brand {
+        #[cfg(debug_assertions)]
+        tag: 0,
+    };
+
+    #[inline(always)]
+    fn next() -> Brand {
+        #[cfg(debug_assertions)]
+        {
+            static NEXT_TAG: AtomicU32 = AtomicU32::new(1);
+
+            let mut tag = NEXT_TAG.fetch_add(1, Ordering::Relaxed);
+
+            // Skipping the unbranded tag on wrap around
+            if tag == 0 {
+                tag = NEXT_TAG.fetch_add(1, Ordering::Relaxed);
+            }
 
-#[repr(transparent)]
-#[derive(Copy, Clone, Eq, PartialEq, Hash, Debug)]
-pub struct Id(usize);
+            Brand { tag }
+        }
+
+        #[cfg(not(debug_assertions))]
+        Brand {}
+    }
 
-impl Id {
+    #[inline(always)]
+    #[track_caller]
+    fn check(self, id: Brand) {
+        #[cfg(debug_assertions)]
+        assert!(
+            id.tag == 0 || id.tag == self.tag,
+            "Id was created by a different Interner"
+        );
+
+        #[cfg(not(debug_assertions))]
+        let _ = id;
+    }
+}
+
+/// A handle to an interned string
+///
+/// `T` tags which `TypedInterner` produced the id, so ids from interners holding
+/// different kinds of symbols cannot be mixed up. `Option<Id<T>>` is the same size as `Id<T>`.
+///
+/// # Note
+/// In debug builds an id remembers the `Interner` that created it and panics when it is
+/// resolved by another one. Ids made by `Id::from_usize` or deserialized are not checked.
+#[cfg_attr(not(debug_assertions), repr(transparent))]
+pub struct Id<T = ()> {
+    raw: NonZeroU32,
+    brand: Brand,
+    _marker: PhantomData<fn() -> T>,
+}
+
+impl<T> Id<T> {
     /// # Safety
-    /// Id's should not be manually created
+    /// Id's should not be manually created,
+    /// `value` must be less than `u32::MAX`
     #[inline(always)]
-    pub unsafe fn from_usize(value: usize) -> Id {
-        Id(value)
+    pub unsafe fn from_usize(value: usize) -> Id<T> {
+        unsafe { Id::from_usize_branded(value, Brand::UNBRANDED) }
+    }
+
+    #[inline(always)]
+    unsafe fn from_usize_branded(value: usize, brand: Brand) -> Id<T> {
+        Id {
+            raw: unsafe { NonZeroU32::new_unchecked(value as u32 + 1) },
+            brand,
+            _marker: PhantomData,
+        }
+    }
+
+    /// The id given to `keywords[index]` by `Interner::with_keywords`
+    #[inline(always)]
+    pub const fn keyword(index: u32) -> Id<T> {
+        assert!(index < u32::MAX, "keyword index out of range");
+
+        Id {
+            raw: NonZeroU32::new(index + 1).unwrap(),
+            brand: Brand::UNBRANDED,
+            _marker: PhantomData,
+        }
+    }
+
+    /// Forgets which `Interner` created this id, so it is accepted by any `Interner`
+    /// holding the same symbols (such as one loaded with `Interner::from_bytes`)
+    #[inline(always)]
+    pub const fn unbranded(self) -> Id<T> {
+        Id {
+            raw: self.raw,
+            brand: Brand::UNBRANDED,
+            _marker: PhantomData,
+        }
+    }
+
+    /// The position of the symbol within its `Interner`,
+    /// this is stable across `Interner::to_bytes` and `Interner::from_bytes`
+    #[inline(always)]
+    pub const fn index(self) -> usize {
+        (self.raw.get() - 1) as usize
+    }
+
+    #[inline(always)]
+    #[track_caller]
+    fn new(index: usize, brand: Brand) -> Id<T> {
+        match u32::try_from(index) {
+            Ok(index) if index < u32::MAX => unsafe {
+                Id::from_usize_branded(index as usize, brand)
+            },
+            _ => panic!("interner overflow, id index {index} does not fit below u32::MAX"),
+        }
+    }
+}
+
+impl<T> Clone for Id<T> {
+    #[inline(always)]
+    fn clone(&self) -> Self {
+        *self
+    }
+}
+
+impl<T> Copy for Id<T> {}
+
+impl<T> PartialEq for Id<T> {
+    #[inline(always)]
+    fn eq(&self, other: &Self) -> bool {
+        self.raw == other.raw
+    }
+}
+
+impl<T> Eq for Id<T> {}
+
+impl<T> PartialOrd for Id<T> {
+    #[inline(always)]
+    fn partial_cmp(&self, other: &Self) -> Option<cmp::Ordering> {
+        Some(self.cmp(other))
+    }
+}
+
+impl<T> Ord for Id<T> {
+    #[inline(always)]
+    fn cmp(&self, other: &Self) -> cmp::Ordering {
+        self.raw.cmp(&other.raw)
+    }
+}
+
+impl<T> Hash for Id<T> {
+    #[inline(always)]
+    fn hash<H: Hasher>(&self, state: &mut H) {
+        self.raw.hash(state)
+    }
+}
+
+impl<T> fmt::Debug for Id<T> {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.debug_tuple("Id").field(&self.index()).finish()
+    }
+}
+
+#[cfg(feature = "serde")]
+impl<T> serde::Serialize for Id<T> {
+    fn serialize<S: serde::Serializer>(&self, serializer: S) -> Result<S::Ok, S::Error> {
+        serializer.serialize_u32(self.index() as u32)
+    }
+}
+
+#[cfg(feature = "serde")]
+impl<'de, T> serde::Deserialize<'de> for Id<T> {
+    fn deserialize<D: serde::Deserializer<'de>>(deserializer: D) -> Result<Self, D::Error> {
+        let index = u32::deserialize(deserializer)?;
+
+        if index == u32::MAX {
+            return Err(serde::de::Error::custom("interner id out of range"));
+        }
+
+        Ok(unsafe { Id::from_usize(index as usize) })
     }
 }
 
-#[derive(Default)]
 pub struct Interner {
-    // This can be static as index will always be dropped before data is dropped
-    index: HashMap<&'static str, Id>,
-    data: Vec<Box<str>>,
+    // Keys point into `arena`, which is never freed while the `Interner` is alive
+    index: HashMap<Symbol, Id>,
+    data: Vec<Symbol>,
+    arena: Arena,
+    // The first `keywords` symbols were given by `Interner::with_keywords`
+    keywords: usize,
+    brand: Brand,
+}
+
+impl Default for Interner {
+    #[inline(always)]
+    fn default() -> Self {
+        Self::new()
+    }
 }
 
 impl Interner {
@@ -25,36 +243,145 @@ impl Interner {
         Interner {
             index: HashMap::new(),
             data: Vec::new(),
+            arena: Arena::new(),
+            keywords: 0,
+            brand: Brand::next(),
+        }
+    }
+
+    /// Reserves room for `strings` symbols made up of `bytes` bytes of text in total
+    pub fn with_capacity(strings: usize, bytes: usize) -> Interner {
+        Interner {
+            index: HashMap::with_capacity(strings),
+            data: Vec::with_capacity(strings),
+            arena: Arena::with_capacity(bytes),
+            keywords: 0,
+            brand: Brand::next(),
+        }
+    }
+
+    /// Interns `keywords` first, so `keywords[i]` is always given `Id::keyword(i)`
+    ///
+    /// # Panics
+    /// If a keyword appears twice
+    pub fn with_keywords(keywords: &[&str]) -> Interner {
+        let mut interner = Interner::with_capacity(
+            keywords.len(),
+            keywords.iter().map(|keyword| keyword.len()).sum(),
+        );
+
+        for (i, keyword) in keywords.iter().enumerate() {
+            assert!(
+                interner.insert(keyword).index() == i,
+                "duplicate keyword `{keyword}`"
+            );
+        }
+
+        interner.keywords = keywords.len();
+        interner
+    }
+
+    /// Interns every keyword of `K::TABLE` in order, see `Interner::with_keywords`
+    #[inline(always)]
+    pub fn with_keyword_table<K: Keyword>() -> Interner {
+        let keywords = K::TABLE
+            .iter()
+            .map(|(keyword, _)| *keyword)
+            .collect::<Vec<_>>();
+        Interner::with_keywords(&keywords)
+    }
+
+    #[inline(always)]
+    pub const fn keyword_count(&self) -> usize {
+        self.keywords
+    }
+
+    #[inline(always)]
+    pub const fn is_keyword(&self, id: Id) -> bool {
+        id.index() < self.keywords
+    }
+
+    /// The position of `id` within the keywords given to `Interner::with_keywords`
+    #[inline(always)]
+    pub const fn keyword_index(&self, id: Id) -> Option<usize> {
+        if self.is_keyword(id) {
+            Some(id.index())
+        } else {
+            None
         }
     }
 
+    /// Classifies `id` using `K::TABLE`, which must be the table this `Interner`
+    /// was created with by `Interner::with_keyword_table`
+    #[inline(always)]
+    pub fn keyword_kind<K: Keyword>(&self, id: Id) -> Option<K> {
+        let index = self.keyword_index(id)?;
+
+        debug_assert_eq!(
+            K::TABLE.get(index).map(|(keyword, _)| *keyword),
+            Some(self.resolve(id)),
+            "keyword table does not match the Interner"
+        );
+
+        K::TABLE.get(index).map(|(_, kind)| *kind)
+    }
+
     pub fn insert(&mut self, string: &str) -> Id {
         if let Some(id) = self.index.get(string) {
             return *id;
         }
 
-        let string = Box::<str>::from(string);
+        let symbol = self.arena.alloc(string);
 
-        let static_ref = unsafe { &*((&*string) as *const str) };
+        self.data.push(symbol);
 
-        self.data.push(string);
-
-        let id = Id(self.data.len() - 1);
-        self.index.insert(static_ref, id);
+        let id = Id::new(self.data.len() - 1, self.brand);
+        self.index.insert(symbol, id);
 
         id
     }
 
+    /// Interns the text of `entry` straight from the input buffer and consumes it
+    #[inline(always)]
+    pub fn insert_entry<I: ?Sized + Input>(&mut self, entry: input::Entry<'_, I>) -> Spanned<Id> {
+        let span = entry.span();
+        let id = self.insert(entry.get());
+
+        entry.consume();
+
+        span.over(id)
+    }
+
+    /// Looks up `string` without interning it
     #[inline(always)]
+    pub fn get(&self, string: &str) -> Option<Id> {
+        self.index.get(string).copied()
+    }
+
+    #[inline(always)]
+    pub fn contains(&self, string: &str) -> bool {
+        self.index.contains_key(string)
+    }
+
+    /// # Panics
+    /// If `id` is out of bounds, or in debug builds if `id` was created by another `Interner`
+    #[inline(always)]
+    #[track_caller]
     pub fn resolve(&self, id: Id) -> &str {
-        &self.data[id.0]
+        self.brand.check(id.brand);
+        unsafe { self.data[id.index()].as_str() }
     }
 
     /// # Safety
-    /// `id` must be less than `self.data.len()`
+    /// `id` must be less than `self.len()`,
+    /// this holds for every `Id` returned by this `Interner` unless it was removed
+    /// by `Interner::truncate_to`
     #[inline(always)]
-    pub fn resolve_unchecked(&self, id: Id) -> &str {
-        unsafe { self.data.get_unchecked(id.0) }
+    #[track_caller]
+    pub unsafe fn resolve_unchecked(&self, id: Id) -> &str {
+        self.brand.check(id.brand);
+        debug_assert!(id.index() < self.data.len(), "Id out of bounds");
+        unsafe { self.data.get_unchecked(id.index()).as_str() }
     }
 
     #[inline(always)]
@@ -66,6 +393,379 @@ impl Interner {
     pub fn is_empty(&self) -> bool {
         self.data.is_empty()
     }
+
+    /// Marks the current set of symbols, see `Interner::truncate_to`
+    #[inline(always)]
+    pub fn checkpoint(&self) -> Checkpoint {
+        Checkpoint {
+            len: self.data.len(),
+            brand: self.brand,
+        }
+    }
+
+    /// Removes every symbol inserted since `checkpoint` was taken and frees their text
+    ///
+    /// # Note
+    /// Ids of removed symbols must not be used afterwards, they will either panic
+    /// or resolve to whichever symbol is later given the same `Id`.
+    ///
+    /// # Panics
+    /// If `checkpoint` is ahead of this `Interner` (it was taken before an earlier rollback
+    /// and more symbols were removed since), if it would remove keywords,
+    /// or in debug builds if it was taken from another `Interner`.
+    #[track_caller]
+    pub fn truncate_to(&mut self, checkpoint: Checkpoint) {
+        self.brand.check(checkpoint.brand);
+
+        assert!(
+            checkpoint.len <= self.data.len(),
+            "checkpoint is ahead of the Interner"
+        );
+        assert!(
+            checkpoint.len >= self.keywords,
+            "checkpoint would remove keywords"
+        );
+
+        for symbol in self.data.drain(checkpoint.len..) {
+            self.index.remove(unsafe { symbol.as_str() });
+        }
+
+        // Symbols are allocated in `Id` order so the last one marks the end of the used arena,
+        // empty symbols are not allocated at all so they cannot mark it
+        let last = self.data.iter().rev().find(|symbol| !symbol.is_empty());
+        self.arena.truncate(last.copied());
+    }
+
+    /// Iterates over every symbol in `Id` order
+    #[inline(always)]
+    pub fn iter(&self) -> Iter<'_> {
+        Iter {
+            inner: self.data.iter().enumerate(),
+            brand: self.brand,
+            _marker: PhantomData,
+        }
+    }
+
+    pub fn memory_usage(&self) -> MemoryUsage {
+        MemoryUsage {
+            strings: self.arena.used(),
+            arena: self.arena.allocated(),
+            table: self.data.capacity() * size_of::<Symbol>()
+                + self.index.capacity() * size_of::<(Symbol, Id)>(),
+        }
+    }
+
+    /// Appends the symbol table to `buf`.
+    ///
+    /// # Format
+    /// The magic bytes `PINT` and a format version byte,
+    /// then a LEB128 symbol count and a LEB128 keyword count followed by every symbol
+    /// in `Id` order, each as a LEB128 byte length and its UTF-8 bytes.
+    pub fn encode(&self, buf: &mut Vec<u8>) {
+        buf.extend_from_slice(FORMAT_MAGIC);
+        buf.push(FORMAT_VERSION);
+        write_varint(buf, self.data.len());
+        write_varint(buf, self.keywords);
+
+        for string in self.data.iter().map(|symbol| unsafe { symbol.as_str() }) {
+            write_varint(buf, string.len());
+            buf.extend_from_slice(string.as_bytes());
+        }
+    }
+
+    #[inline(always)]
+    pub fn to_bytes(&self) -> Vec<u8> {
+        let mut buf = Vec::new();
+        self.encode(&mut buf);
+        buf
+    }
+
+    /// Rebuilds an `Interner` written by `Interner::encode`,
+    /// every `Id` resolves to the same symbol as it did before encoding.
+    ///
+    /// # Note
+    /// Ids still held from the original `Interner` must go through `Id::unbranded`
+    /// to be accepted in debug builds, deserialized ids are already unbranded.
+    pub fn from_bytes(bytes: &[u8]) -> Result<Interner, DecodeError> {
+        let Some((FORMAT_MAGIC, [version, ..])) = bytes.split_first_chunk() else {
+            return Err(DecodeError::UnsupportedFormat);
+        };
+
+        if *version != FORMAT_VERSION {
+            return Err(DecodeError::UnsupportedFormat);
+        }
+
+        let mut cursor = FORMAT_MAGIC.len() + 1;
+
+        let count = read_varint(bytes, &mut cursor)?;
+        let keywords = read_varint(bytes, &mut cursor)?;
+
+        if keywords > count {
+            return Err(DecodeError::InvalidKeywordCount);
+        }
+
+        let mut interner = Interner::new();
+        interner.keywords = keywords;
+
+        for i in 0..count {
+            let len = read_varint(bytes, &mut cursor)?;
+
+            let end = cursor
+                .checked_add(len)
+                .filter(|end| *end <= bytes.len())
+                .ok_or(DecodeError::UnexpectedEnd)?;
+
+            let string = str::from_utf8(&bytes[cursor..end])
+                .map_err(|_| DecodeError::InvalidUtf8 { index: cursor })?;
+
+            cursor = end;
+
+            if interner.insert(string).index() != i {
+                return Err(DecodeError::Duplicate {
+                    id: Id::new(i, Brand::UNBRANDED),
+                });
+            }
+        }
+
+        if cursor != bytes.len() {
+            return Err(DecodeError::TrailingBytes { index: cursor });
+        }
+
+        Ok(interner)
+    }
+}
+
+/// A fixed set of reserved words, usually implemented by a fieldless enum
+pub trait Keyword: Copy + 'static {
+    /// Every keyword spelling, in `Id` order
+    const TABLE: &'static [(&'static str, Self)];
+}
+
+/// The length of an `Interner` at some point in time, see `Interner::checkpoint`
+#[derive(Clone, Copy)]
+pub struct Checkpoint {
+    len: usize,
+    brand: Brand,
+}
+
+impl Checkpoint {
+    /// The number of symbols that will remain after rolling back to this checkpoint
+    #[inline(always)]
+    pub const fn symbols(&self) -> usize {
+        self.len
+    }
+}
+
+impl fmt::Debug for Checkpoint {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.debug_struct("Checkpoint")
+            .field("len", &self.len)
+            .finish()
+    }
+}
+
+impl<'a> IntoIterator for &'a Interner {
+    type Item = (Id, &'a str);
+    type IntoIter = Iter<'a>;
+
+    #[inline(always)]
+    fn into_iter(self) -> Iter<'a> {
+        self.iter()
+    }
+}
+
+pub struct Iter<'a, T = ()> {
+    inner: Enumerate<slice::Iter<'a, Symbol>>,
+    brand: Brand,
+    _marker: PhantomData<fn() -> T>,
+}
+
+impl<'a, T> Iterator for Iter<'a, T> {
+    type Item = (Id<T>, &'a str);
+
+    #[inline(always)]
+    fn next(&mut self) -> Option<Self::Item> {
+        self.inner.next().map(|(i, symbol)| {
+            (unsafe { Id::from_usize_branded(i, self.brand) }, unsafe {
+                symbol.as_str()
+            })
+        })
+    }
+
+    #[inline(always)]
+    fn size_hint(&self) -> (usize, Option<usize>) {
+        self.inner.size_hint()
+    }
+}
+
+impl<'a, T> DoubleEndedIterator for Iter<'a, T> {
+    #[inline(always)]
+    fn next_back(&mut self) -> Option<Self::Item> {
+        self.inner.next_back().map(|(i, symbol)| {
+            (unsafe { Id::from_usize_branded(i, self.brand) }, unsafe {
+                symbol.as_str()
+            })
+        })
+    }
+}
+
+impl<'a, T> ExactSizeIterator for Iter<'a, T> {}
+
+impl<'a, T> FusedIterator for Iter<'a, T> {}
+
+/// Heap memory held by an `Interner`, in bytes
+#[derive(Default, Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub struct MemoryUsage {
+    /// Text of every interned string
+    pub strings: usize,
+    /// Arena pages reserved for string text, including unused space
+    pub arena: usize,
+    /// The id to string table and the string to id map
+    pub table: usize,
+}
+
+impl MemoryUsage {
+    #[inline(always)]
+    pub const fn total(&self) -> usize {
+        self.arena + self.table
+    }
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub enum DecodeError {
+    UnexpectedEnd,
+    /// A symbol was not valid UTF-8, `index` is the byte offset of the symbol
+    InvalidUtf8 {
+        index: usize,
+    },
+    /// A symbol appeared twice, so `Id` numbering could not be preserved
+    Duplicate {
+        id: Id,
+    },
+    /// The symbol table ended before the end of the input
+    TrailingBytes {
+        index: usize,
+    },
+    /// A length did not fit in a `usize`
+    Overflow,
+    /// There were more keywords than symbols
+    InvalidKeywordCount,
+    /// The data did not start with the magic bytes and version of this format
+    UnsupportedFormat,
+}
+
+impl fmt::Display for DecodeError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            DecodeError::UnexpectedEnd => write!(f, "unexpected end of interner data"),
+            DecodeError::InvalidUtf8 { index } => {
+                write!(f, "invalid utf-8 in interned symbol at byte {index}")
+            }
+            DecodeError::Duplicate { id } => {
+                write!(f, "duplicate interned symbol for id {}", id.index())
+            }
+            DecodeError::TrailingBytes { index } => {
+                write!(f, "trailing bytes after interner data at byte {index}")
+            }
+            DecodeError::Overflow => write!(f, "length overflow in interner data"),
+            DecodeError::InvalidKeywordCount => {
+                write!(f, "more keywords than symbols in interner data")
+            }
+            DecodeError::UnsupportedFormat => {
+                write!(f, "interner data has an unknown magic or format version")
+            }
+        }
+    }
+}
+
+impl error::Error for DecodeError {}
+
+const FORMAT_MAGIC: &[u8; 4] = b"PINT";
+/// Bumped on every incompatible change to the serialized format of an `Interner`
+const FORMAT_VERSION: u8 = 1;
+
+fn write_varint(buf: &mut Vec<u8>, mut value: usize) {
+    while value >= 0x80 {
+        buf.push(value as u8 | 0x80);
+        value >>= 7;
+    }
+
+    buf.push(value as u8);
+}
+
+fn read_varint(bytes: &[u8], cursor: &mut usize) -> Result<usize, DecodeError> {
+    let mut value = 0usize;
+    let mut shift = 0;
+
+    loop {
+        let byte = *bytes.get(*cursor).ok_or(DecodeError::UnexpectedEnd)?;
+        *cursor += 1;
+
+        let bits = (byte & 0x7f) as usize;
+
+        if shift >= usize::BITS || (bits << shift) >> shift != bits {
+            return Err(DecodeError::Overflow);
+        }
+
+        value |= bits << shift;
+        shift += 7;
+
+        if byte & 0x80 == 0 {
+            return Ok(value);
+        }
+    }
+}
+
+#[cfg(feature = "serde")]
+impl serde::Serialize for Interner {
+    fn serialize<S: serde::Serializer>(&self, serializer: S) -> Result<S::Ok, S::Error> {
+        use serde::ser::SerializeTuple;
+
+        struct Symbols<'a>(&'a Interner);
+
+        impl serde::Serialize for Symbols<'_> {
+            fn serialize<S: serde::Serializer>(&self, serializer: S) -> Result<S::Ok, S::Error> {
+                serializer.collect_seq(self.0.iter().map(|(_, string)| string))
+            }
+        }
+
+        let mut tuple = serializer.serialize_tuple(3)?;
+        tuple.serialize_element(&FORMAT_VERSION)?;
+        tuple.serialize_element(&self.keywords)?;
+        tuple.serialize_element(&Symbols(self))?;
+        tuple.end()
+    }
+}
+
+#[cfg(feature = "serde")]
+impl<'de> serde::Deserialize<'de> for Interner {
+    fn deserialize<D: serde::Deserializer<'de>>(deserializer: D) -> Result<Self, D::Error> {
+        use serde::de::Error;
+
+        let (version, keywords, symbols) =
+            <(u8, usize, Vec<std::borrow::Cow<'de, str>>)>::deserialize(deserializer)?;
+
+        if version != FORMAT_VERSION {
+            return Err(D::Error::custom(DecodeError::UnsupportedFormat));
+        }
+
+        if keywords > symbols.len() {
+            return Err(D::Error::custom(DecodeError::InvalidKeywordCount));
+        }
+
+        let mut interner = Interner::with_capacity(symbols.len(), 0);
+        interner.keywords = keywords;
+
+        for (i, string) in symbols.iter().enumerate() {
+            if interner.insert(string).index() != i {
+                return Err(D::Error::custom(DecodeError::Duplicate {
+                    id: Id::new(i, Brand::UNBRANDED),
+                }));
+            }
+        }
+
+        Ok(interner)
+    }
 }
 
 #[cfg(test)]
@@ -90,8 +790,8 @@ mod tests {
         let a = interner.insert("a");
         let b = interner.insert("b");
 
-        assert_eq!(interner.resolve_unchecked(a), "a");
-        assert_eq!(interner.resolve_unchecked(b), "b");
+        assert_eq!(unsafe { interner.resolve_unchecked(a) }, "a");
+        assert_eq!(unsafe { interner.resolve_unchecked(b) }, "b");
     }
 
     #[test]
@@ -139,4 +839,367 @@ mod tests {
 
         assert_eq!(interner.resolve(a), "hello");
     }
+
+    #[test]
+    fn lookup_without_insert() {
+        let mut interner = Interner::new();
+
+        let a = interner.insert("a");
+
+        assert_eq!(interner.get("a"), Some(a));
+        assert_eq!(interner.get("b"), None);
+        assert!(interner.contains("a"));
+        assert!(!interner.contains("b"));
+        assert_eq!(interner.len(), 1);
+    }
+
+    #[test]
+    fn correct_iter() {
+        let mut interner = Interner::new();
+
+        let a = interner.insert("a");
+        let b = interner.insert("b");
+        interner.insert("a");
+
+        assert_eq!(interner.iter().collect::<Vec<_>>(), [(a, "a"), (b, "b")]);
+        assert_eq!(interner.iter().next_back(), Some((b, "b")));
+        assert_eq!(interner.iter().len(), 2);
+    }
+
+    #[test]
+    fn niche_optimised() {
+        assert_eq!(size_of::<Option<Id>>(), size_of::<Id>());
+
+        #[cfg(not(debug_assertions))]
+        assert_eq!(size_of::<Option<Id<String>>>(), 4);
+    }
+
+    #[test]
+    #[cfg(debug_assertions)]
+    #[should_panic(expected = "Id was created by a different Interner")]
+    fn foreign_id() {
+        let mut a = Interner::new();
+        let mut b = Interner::new();
+
+        a.insert("a");
+        let id = b.insert("b");
+
+        a.resolve(id);
+    }
+
+    #[test]
+    fn unbranded_id() {
+        let mut interner = Interner::new();
+
+        let id = interner.insert("a");
+
+        assert_eq!(interner.resolve(id.unbranded()), "a");
+        assert_eq!(interner.resolve(unsafe { Id::from_usize(0) }), "a");
+    }
+
+    #[test]
+    fn fixed_keyword_ids() {
+        const IF: Id = Id::keyword(0);
+        const ELSE: Id = Id::keyword(1);
+
+        let mut interner = Interner::with_keywords(&["if", "else"]);
+
+        let iffy = interner.insert("iffy");
+
+        assert_eq!(interner.insert("if"), IF);
+        assert_eq!(interner.insert("else"), ELSE);
+        assert_eq!(interner.keyword_count(), 2);
+
+        assert!(interner.is_keyword(IF));
+        assert!(!interner.is_keyword(iffy));
+        assert_eq!(interner.keyword_index(ELSE), Some(1));
+        assert_eq!(interner.keyword_index(iffy), None);
+
+        let loaded = Interner::from_bytes(&interner.to_bytes()).unwrap();
+
+        assert!(loaded.is_keyword(ELSE));
+        assert!(!loaded.is_keyword(iffy.unbranded()));
+    }
+
+    #[test]
+    fn keyword_table() {
+        #[derive(Clone, Copy, PartialEq, Debug)]
+        enum Kw {
+            If,
+            Else,
+        }
+
+        impl Keyword for Kw {
+            const TABLE: &'static [(&'static str, Self)] = &[("if", Kw::If), ("else", Kw::Else)];
+        }
+
+        let mut interner = Interner::with_keyword_table::<Kw>();
+
+        let else_id = interner.insert("else");
+        let ident = interner.insert("ident");
+
+        assert_eq!(interner.keyword_kind::<Kw>(else_id), Some(Kw::Else));
+        assert_eq!(interner.keyword_kind::<Kw>(ident), None);
+        assert_eq!(interner.resolve(Id::keyword(0)), "if");
+    }
+
+    #[test]
+    #[should_panic(expected = "duplicate keyword `if`")]
+    fn duplicate_keyword() {
+        Interner::with_keywords(&["if", "if"]);
+    }
+
+    #[test]
+    #[should_panic(expected = "interner overflow")]
+    fn id_overflow() {
+        Id::<()>::new(u32::MAX as usize, Brand::UNBRANDED);
+    }
+
+    #[test]
+    fn truncate_to_checkpoint() {
+        let mut interner = Interner::with_capacity(0, 16);
+
+        let a = interner.insert("a");
+
+        let checkpoint = interner.checkpoint();
+        let used = interner.memory_usage().strings;
+
+        let speculative = (0..1000)
+            .map(|i| interner.insert(&format!("speculative_{i}")))
+            .collect::<Vec<_>>();
+
+        assert_eq!(interner.len(), 1001);
+
+        interner.truncate_to(checkpoint);
+
+        assert_eq!(interner.len(), 1);
+        assert_eq!(interner.memory_usage().strings, used);
+        assert_eq!(interner.resolve(a), "a");
+        assert_eq!(interner.get("speculative_0"), None);
+        assert!(!interner.contains("speculative_999"));
+
+        let b = interner.insert("b");
+
+        assert_eq!(b, speculative[0]);
+        assert_eq!(interner.resolve(b), "b");
+        assert_eq!(interner.insert("a"), a);
+        assert_eq!(interner.iter().collect::<Vec<_>>(), [(a, "a"), (b, "b")]);
+    }
+
+    #[test]
+    fn truncate_to_empty() {
+        let mut interner = Interner::new();
+
+        let checkpoint = interner.checkpoint();
+
+        interner.insert("a");
+        interner.insert("");
+        interner.truncate_to(checkpoint);
+
+        assert!(interner.is_empty());
+        assert_eq!(interner.memory_usage().strings, 0);
+
+        let a = interner.insert("a");
+        assert_eq!(interner.resolve(a), "a");
+    }
+
+    #[test]
+    fn truncate_after_empty() {
+        let mut interner = Interner::new();
+
+        let a = interner.insert("aaaa");
+        interner.insert("");
+
+        let checkpoint = interner.checkpoint();
+        interner.insert("bbbb");
+        interner.truncate_to(checkpoint);
+
+        let c = interner.insert("cccc");
+
+        assert_eq!(interner.resolve(a), "aaaa");
+        assert_eq!(interner.resolve(c), "cccc");
+        assert_eq!(interner.memory_usage().strings, 8);
+    }
+
+    #[test]
+    fn nested_checkpoints() {
+        let mut interner = Interner::new();
+
+        let outer = interner.checkpoint();
+        interner.insert("a");
+
+        let inner = interner.checkpoint();
+        interner.insert("b");
+
+        interner.truncate_to(inner);
+        assert_eq!(interner.len(), 1);
+
+        interner.insert("c");
+        interner.truncate_to(outer);
+        assert!(interner.is_empty());
+    }
+
+    #[test]
+    #[should_panic(expected = "checkpoint is ahead of the Interner")]
+    fn stale_checkpoint() {
+        let mut interner = Interner::new();
+
+        let outer = interner.checkpoint();
+        interner.insert("a");
+        let inner = interner.checkpoint();
+
+        interner.truncate_to(outer);
+        interner.truncate_to(inner);
+    }
+
+    #[test]
+    fn stable_across_pages() {
+        // Still enough symbols to fill several pages, without taking minutes under Miri
+        const COUNT: usize = if cfg!(miri) { 1000 } else { 10_000 };
+
+        let mut interner = Interner::with_capacity(0, 16);
+
+        let ids = (0..COUNT)
+            .map(|i| interner.insert(&format!("symbol_{i}")))
+            .collect::<Vec<_>>();
+
+        for (i, id) in ids.into_iter().enumerate() {
+            assert_eq!(interner.resolve(id), format!("symbol_{i}"));
+            assert_eq!(interner.insert(&format!("symbol_{i}")), id);
+        }
+
+        assert_eq!(interner.len(), COUNT);
+    }
+
+    #[test]
+    fn large_symbol() {
+        let mut interner = Interner::new();
+
+        let large = "x".repeat(100_000);
+
+        let a = interner.insert("a");
+        let b = interner.insert(&large);
+        let c = interner.insert("c");
+
+        assert_eq!(interner.resolve(a), "a");
+        assert_eq!(interner.resolve(b), large);
+        assert_eq!(interner.resolve(c), "c");
+    }
+
+    #[test]
+    fn correct_memory_usage() {
+        let mut interner = Interner::with_capacity(2, 64);
+
+        interner.insert("hello");
+        interner.insert("world");
+        interner.insert("hello");
+
+        let usage = interner.memory_usage();
+
+        assert_eq!(usage.strings, 10);
+        assert_eq!(usage.arena, 64);
+        assert!(usage.total() > usage.arena);
+    }
+
+    #[test]
+    fn is_send_sync() {
+        fn assert_send_sync<T: Send + Sync>() {}
+
+        assert_send_sync::<Interner>();
+    }
+
+    #[test]
+    fn bytes_round_trip() {
+        let mut interner = Interner::new();
+
+        let a = interner.insert("a");
+        let party = interner.insert("🎉");
+        let empty = interner.insert("");
+
+        let bytes = interner.to_bytes();
+        let loaded = Interner::from_bytes(&bytes).unwrap();
+
+        assert_eq!(loaded.len(), 3);
+        assert_eq!(loaded.resolve(a.unbranded()), "a");
+        assert_eq!(loaded.resolve(party.unbranded()), "🎉");
+        assert_eq!(loaded.resolve(empty.unbranded()), "");
+    }
+
+    #[test]
+    #[cfg(feature = "serde")]
+    fn serde_round_trip() {
+        let mut interner = Interner::with_keywords(&["if", "else"]);
+
+        let a = interner.insert("a");
+        let party = interner.insert("🎉");
+
+        let json = serde_json::to_string(&interner).unwrap();
+        assert_eq!(json, r#"[1,2,["if","else","a","🎉"]]"#);
+
+        let loaded: Interner = serde_json::from_str(&json).unwrap();
+
+        assert_eq!(loaded.len(), 4);
+        assert_eq!(loaded.keyword_count(), 2);
+        assert_eq!(loaded.resolve(Id::keyword(1)), "else");
+        assert_eq!(loaded.resolve(a.unbranded()), "a");
+        assert_eq!(loaded.resolve(party.unbranded()), "🎉");
+
+        let id: Id = serde_json::from_str(&serde_json::to_string(&a).unwrap()).unwrap();
+        assert_eq!(id, a.unbranded());
+
+        for json in [r#"[2,0,[]]"#, r#"[1,2,["if"]]"#, r#"[1,0,["a","a"]]"#] {
+            assert!(serde_json::from_str::<Interner>(json).is_err());
+        }
+    }
+
+    #[test]
+    fn bytes_rejects_invalid() {
+        let from_bytes = |bytes: &[u8]| {
+            let mut data = b"PINT\x01".to_vec();
+            data.extend_from_slice(bytes);
+
+            Interner::from_bytes(&data).err()
+        };
+
+        assert_eq!(from_bytes(&[]), Some(DecodeError::UnexpectedEnd));
+        assert_eq!(
+            from_bytes(&[1, 0, 2, b'a']),
+            Some(DecodeError::UnexpectedEnd)
+        );
+        assert_eq!(
+            from_bytes(&[1, 2, 1, b'a']),
+            Some(DecodeError::InvalidKeywordCount)
+        );
+        assert_eq!(
+            from_bytes(&[2, 0, 1, b'a', 1, b'a']),
+            Some(DecodeError::Duplicate {
+                id: Id::new(1, Brand::UNBRANDED)
+            })
+        );
+        assert_eq!(
+            from_bytes(&[1, 0, 1, 0xff]),
+            Some(DecodeError::InvalidUtf8 { index: 8 })
+        );
+        assert_eq!(
+            from_bytes(&[0, 0, 0]),
+            Some(DecodeError::TrailingBytes { index: 7 })
+        );
+    }
+
+    #[test]
+    fn bytes_rejects_other_formats() {
+        for bytes in [
+            &b""[..],
+            b"PIN",
+            b"PINT",
+            b"PINT\x00\x00\x00",
+            b"PINT\x02\x00\x00",
+            &[1, 0, 1, b'a'],
+        ] {
+            assert_eq!(
+                Interner::from_bytes(bytes).err(),
+                Some(DecodeError::UnsupportedFormat)
+            );
+        }
+    }
 }
@@ -0,0 +1,222 @@
+use std::{
+    alloc::{self, Layout},
+    borrow::Borrow,
+    hash::{Hash, Hasher},
+    ptr::{self, NonNull},
+    slice, str,
+};
+
+const MIN_PAGE_SIZE: usize = 4096;
+const MAX_PAGE_SIZE: usize = 1 << 20;
+
+/// A pointer to a string stored within an `Arena`
+///
+/// # Note
+/// A `Symbol` does not own its data, it is only valid for as long as the `Arena` it
+/// was allocated in, which is why all access to the string is `unsafe`.
+#[derive(Clone, Copy)]
+pub(super) struct Symbol {
+    ptr: NonNull<u8>,
+    len: usize,
+}
+
+// The bytes behind a `Symbol` are never mutated after allocation
+unsafe impl Send for Symbol {}
+unsafe impl Sync for Symbol {}
+
+impl Symbol {
+    const EMPTY: Symbol = Symbol {
+        ptr: NonNull::dangling(),
+        len: 0,
+    };
+
+    #[inline(always)]
+    pub(super) const fn is_empty(self) -> bool {
+        self.len == 0
+    }
+
+    /// # Safety
+    /// The `Arena` this symbol was allocated in must outlive `'a`
+    #[inline(always)]
+    pub(super) unsafe fn as_str<'a>(self) -> &'a str {
+        unsafe { str::from_utf8_unchecked(slice::from_raw_parts(self.ptr.as_ptr(), self.len)) }
+    }
+}
+
+// Symbols are only ever used as keys while their `Arena` is alive,
+// so hashing and comparing through the string is sound.
+impl Borrow<str> for Symbol {
+    #[inline(always)]
+    fn borrow(&self) -> &str {
+        unsafe { self.as_str() }
+    }
+}
+
+impl Hash for Symbol {
+    #[inline(always)]
+    fn hash<H: Hasher>(&self, state: &mut H) {
+        <Self as Borrow<str>>::borrow(self).hash(state)
+    }
+}
+
+impl PartialEq for Symbol {
+    #[inline(always)]
+    fn eq(&self, other: &Self) -> bool {
+        <Self as Borrow<str>>::borrow(self) == <Self as Borrow<str>>::borrow(other)
+    }
+}
+
+impl Eq for Symbol {}
+
+struct Page {
+    ptr: NonNull<u8>,
+    cap: usize,
+    len: usize,
+}
+
+/// Bump allocated pages of string data,
+/// pages are never moved or resized so every `Symbol` has a stable address.
+pub(super) struct Arena {
+    pages: Vec<Page>,
+    next_page_size: usize,
+}
+
+unsafe impl Send for Arena {}
+unsafe impl Sync for Arena {}
+
+impl Default for Arena {
+    #[inline(always)]
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+impl Arena {
+    #[inline(always)]
+    pub(super) const fn new() -> Self {
+        Self {
+            pages: Vec::new(),
+            next_page_size: MIN_PAGE_SIZE,
+        }
+    }
+
+    pub(super) fn with_capacity(bytes: usize) -> Self {
+        let mut arena = Self::new();
+
+        if bytes > 0 {
+            arena.push_page(bytes);
+        }
+
+        arena
+    }
+
+    pub(super) fn alloc(&mut self, string: &str) -> Symbol {
+        let len = string.len();
+
+        if len == 0 {
+            return Symbol::EMPTY;
+        }
+
+        let page = match self.pages.last_mut() {
+            Some(page) if page.cap - page.len >= len => page,
+            _ => {
+                let cap = self.next_page_size.max(len);
+                self.next_page_size = (self.next_page_size * 2).min(MAX_PAGE_SIZE);
+
+                self.push_page(cap)
+            }
+        };
+
+        // Writing through the page pointer without creating a reference to the page
+        // keeps every previously handed out `Symbol` valid.
+        let ptr = unsafe {
+            let dst = page.ptr.as_ptr().add(page.len);
+            ptr::copy_nonoverlapping(string.as_ptr(), dst, len);
+            NonNull::new_unchecked(dst)
+        };
+
+        page.len += len;
+
+        Symbol { ptr, len }
+    }
+
+    fn push_page(&mut self, cap: usize) -> &mut Page {
+        let layout = Layout::array::<u8>(cap).expect("arena page too large");
+
+        let ptr = match NonNull::new(unsafe { alloc::alloc(layout) }) {
+            Some(ptr) => ptr,
+            None => alloc::handle_alloc_error(layout),
+        };
+
+        self.pages.push(Page { ptr, cap, len: 0 });
+        self.pages.last_mut().unwrap()
+    }
+
+    /// Frees every byte allocated after `last`, which must be the most recently allocated
+    /// non-empty `Symbol` that is still in use, or `None` if no such symbol is in use.
+    pub(super) fn truncate(&mut self, last: Option<Symbol>) {
+        let Some(last) = last.filter(|symbol| symbol.len > 0) else {
+            self.truncate_pages(0);
+            return;
+        };
+
+        let addr = last.ptr.as_ptr().addr();
+
+        let page = self
+            .pages
+            .iter()
+            .rposition(|page| {
+                let start = page.ptr.as_ptr().addr();
+                (start..start + page.cap).contains(&addr)
+            })
+            .expect("symbol was not allocated in this arena");
+
+        self.truncate_pages(page + 1);
+
+        let page = &mut self.pages[page];
+        page.len = addr + last.len - page.ptr.as_ptr().addr();
+    }
+
+    fn truncate_pages(&mut self, len: usize) {
+        // Keeping the first page around avoids reallocating it for every rollback
+        for page in self.pages.drain(len.max(1).min(self.pages.len())..) {
+            unsafe {
+                alloc::dealloc(
+                    page.ptr.as_ptr(),
+                    Layout::array::<u8>(page.cap).unwrap_unchecked(),
+                )
+            };
+        }
+
+        if len == 0
+            && let Some(page) = self.pages.first_mut()
+        {
+            page.len = 0;
+        }
+    }
+
+    /// Bytes reserved by every page
+    #[inline(always)]
+    pub(super) fn allocated(&self) -> usize {
+        self.pages.iter().map(|page| page.cap).sum()
+    }
+
+    /// Bytes occupied by strings
+    #[inline(always)]
+    pub(super) fn used(&self) -> usize {
+        self.pages.iter().map(|page| page.len).sum()
+    }
+}
+
+impl Drop for Arena {
+    fn drop(&mut self) {
+        for page in &self.pages {
+            unsafe {
+                alloc::dealloc(
+                    page.ptr.as_ptr(),
+                    Layout::array::<u8>(page.cap).unwrap_unchecked(),
+                )
+            };
+        }
+    }
+}
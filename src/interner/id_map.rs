@@ -0,0 +1,525 @@
+use std::{
+    fmt,
+    iter::{Enumerate, FusedIterator},
+    marker::PhantomData,
+    mem,
+    ops::{Index, IndexMut},
+    slice,
+};
+
+use super::{Brand, Id};
+
+/// A value for every `Id`, stored densely by `Id::index`
+///
+/// # Note
+/// Ids handed out by `IdVec` and `IdMap` are unbranded, see `Id::unbranded`.
+pub struct IdVec<V, K = ()> {
+    values: Vec<V>,
+    _marker: PhantomData<fn() -> K>,
+}
+
+impl<V: Clone, K> Clone for IdVec<V, K> {
+    #[inline(always)]
+    fn clone(&self) -> Self {
+        Self {
+            values: self.values.clone(),
+            _marker: PhantomData,
+        }
+    }
+}
+
+impl<V: fmt::Debug, K> fmt::Debug for IdVec<V, K> {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.debug_map().entries(self.iter()).finish()
+    }
+}
+
+impl<V, K> Default for IdVec<V, K> {
+    #[inline(always)]
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+impl<V, K> IdVec<V, K> {
+    #[inline(always)]
+    pub const fn new() -> Self {
+        Self {
+            values: Vec::new(),
+            _marker: PhantomData,
+        }
+    }
+
+    #[inline(always)]
+    pub fn with_capacity(capacity: usize) -> Self {
+        Self {
+            values: Vec::with_capacity(capacity),
+            _marker: PhantomData,
+        }
+    }
+
+    /// Stores the value for the next `Id`, usually the one most recently given by an `Interner`
+    ///
+    /// # Panics
+    /// If there is no next `Id`, as every `u32` index but `u32::MAX` is taken
+    #[inline(always)]
+    #[track_caller]
+    pub fn push(&mut self, value: V) -> Id<K> {
+        let id = Id::new(self.values.len(), Brand::UNBRANDED);
+        self.values.push(value);
+        id
+    }
+
+    /// Grows or shrinks to hold exactly `len` values, such as `Interner::len`
+    #[inline(always)]
+    pub fn resize_with(&mut self, len: usize, f: impl FnMut() -> V) {
+        self.values.resize_with(len, f)
+    }
+
+    #[inline(always)]
+    pub fn get(&self, id: Id<K>) -> Option<&V> {
+        self.values.get(id.index())
+    }
+
+    #[inline(always)]
+    pub fn get_mut(&mut self, id: Id<K>) -> Option<&mut V> {
+        self.values.get_mut(id.index())
+    }
+
+    #[inline(always)]
+    pub fn len(&self) -> usize {
+        self.values.len()
+    }
+
+    #[inline(always)]
+    pub fn is_empty(&self) -> bool {
+        self.values.is_empty()
+    }
+
+    #[inline(always)]
+    pub fn iter(&self) -> IdVecIter<'_, V, K> {
+        IdVecIter {
+            inner: self.values.iter().enumerate(),
+            _marker: PhantomData,
+        }
+    }
+
+    #[inline(always)]
+    pub fn values(&self) -> slice::Iter<'_, V> {
+        self.values.iter()
+    }
+
+    #[inline(always)]
+    pub fn values_mut(&mut self) -> slice::IterMut<'_, V> {
+        self.values.iter_mut()
+    }
+}
+
+impl<V, K> Index<Id<K>> for IdVec<V, K> {
+    type Output = V;
+
+    #[inline(always)]
+    fn index(&self, id: Id<K>) -> &V {
+        &self.values[id.index()]
+    }
+}
+
+impl<V, K> IndexMut<Id<K>> for IdVec<V, K> {
+    #[inline(always)]
+    fn index_mut(&mut self, id: Id<K>) -> &mut V {
+        &mut self.values[id.index()]
+    }
+}
+
+impl<V, K> FromIterator<V> for IdVec<V, K> {
+    #[inline(always)]
+    fn from_iter<T: IntoIterator<Item = V>>(iter: T) -> Self {
+        Self {
+            values: Vec::from_iter(iter),
+            _marker: PhantomData,
+        }
+    }
+}
+
+impl<'a, V, K> IntoIterator for &'a IdVec<V, K> {
+    type Item = (Id<K>, &'a V);
+    type IntoIter = IdVecIter<'a, V, K>;
+
+    #[inline(always)]
+    fn into_iter(self) -> IdVecIter<'a, V, K> {
+        self.iter()
+    }
+}
+
+pub struct IdVecIter<'a, V, K = ()> {
+    inner: Enumerate<slice::Iter<'a, V>>,
+    _marker: PhantomData<fn() -> K>,
+}
+
+impl<'a, V, K> Iterator for IdVecIter<'a, V, K> {
+    type Item = (Id<K>, &'a V);
+
+    #[inline(always)]
+    fn next(&mut self) -> Option<Self::Item> {
+        self.inner
+            .next()
+            .map(|(i, value)| (Id::new(i, Brand::UNBRANDED), value))
+    }
+
+    #[inline(always)]
+    fn size_hint(&self) -> (usize, Option<usize>) {
+        self.inner.size_hint()
+    }
+}
+
+impl<'a, V, K> ExactSizeIterator for IdVecIter<'a, V, K> {}
+
+impl<'a, V, K> FusedIterator for IdVecIter<'a, V, K> {}
+
+/// A value for some `Id`s, stored densely by `Id::index`
+///
+/// # Note
+/// Memory use grows with the largest `Id` inserted rather than the number of values,
+/// which suits per-symbol data where most symbols end up with a value.
+pub struct IdMap<V, K = ()> {
+    values: IdVec<Option<V>, K>,
+    len: usize,
+}
+
+impl<V: Clone, K> Clone for IdMap<V, K> {
+    #[inline(always)]
+    fn clone(&self) -> Self {
+        Self {
+            values: self.values.clone(),
+            len: self.len,
+        }
+    }
+}
+
+impl<V: fmt::Debug, K> fmt::Debug for IdMap<V, K> {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.debug_map().entries(self.iter()).finish()
+    }
+}
+
+impl<V, K> Default for IdMap<V, K> {
+    #[inline(always)]
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+impl<V, K> IdMap<V, K> {
+    #[inline(always)]
+    pub const fn new() -> Self {
+        Self {
+            values: IdVec::new(),
+            len: 0,
+        }
+    }
+
+    /// Reserves room for ids below `capacity`, such as `Interner::len`
+    #[inline(always)]
+    pub fn with_capacity(capacity: usize) -> Self {
+        Self {
+            values: IdVec::with_capacity(capacity),
+            len: 0,
+        }
+    }
+
+    pub fn insert(&mut self, id: Id<K>, value: V) -> Option<V> {
+        if id.index() >= self.values.len() {
+            self.values.resize_with(id.index() + 1, || None);
+        }
+
+        let old = self.values[id].replace(value);
+
+        if old.is_none() {
+            self.len += 1;
+        }
+
+        old
+    }
+
+    pub fn remove(&mut self, id: Id<K>) -> Option<V> {
+        let old = self.values.get_mut(id)?.take();
+
+        if old.is_some() {
+            self.len -= 1;
+        }
+
+        old
+    }
+
+    #[inline(always)]
+    pub fn get(&self, id: Id<K>) -> Option<&V> {
+        self.values.get(id)?.as_ref()
+    }
+
+    #[inline(always)]
+    pub fn get_mut(&mut self, id: Id<K>) -> Option<&mut V> {
+        self.values.get_mut(id)?.as_mut()
+    }
+
+    #[inline(always)]
+    pub fn contains_key(&self, id: Id<K>) -> bool {
+        self.get(id).is_some()
+    }
+
+    pub fn entry(&mut self, id: Id<K>) -> Entry<'_, V, K> {
+        if id.index() >= self.values.len() {
+            self.values.resize_with(id.index() + 1, || None);
+        }
+
+        let slot = &mut self.values[id];
+        let len = &mut self.len;
+
+        if slot.is_some() {
+            Entry::Occupied(OccupiedEntry { id, slot, len })
+        } else {
+            Entry::Vacant(VacantEntry { id, slot, len })
+        }
+    }
+
+    /// The number of ids with a value
+    #[inline(always)]
+    pub const fn len(&self) -> usize {
+        self.len
+    }
+
+    #[inline(always)]
+    pub const fn is_empty(&self) -> bool {
+        self.len == 0
+    }
+
+    #[inline(always)]
+    pub fn clear(&mut self) {
+        *self = Self::new();
+    }
+
+    /// Iterates over every id with a value in `Id` order
+    #[inline(always)]
+    pub fn iter(&self) -> impl DoubleEndedIterator<Item = (Id<K>, &V)> {
+        self.values
+            .values
+            .iter()
+            .enumerate()
+            .filter_map(|(i, value)| Some((Id::new(i, Brand::UNBRANDED), value.as_ref()?)))
+    }
+
+    #[inline(always)]
+    pub fn iter_mut(&mut self) -> impl DoubleEndedIterator<Item = (Id<K>, &mut V)> {
+        self.values
+            .values
+            .iter_mut()
+            .enumerate()
+            .filter_map(|(i, value)| Some((Id::new(i, Brand::UNBRANDED), value.as_mut()?)))
+    }
+}
+
+impl<V, K> Index<Id<K>> for IdMap<V, K> {
+    type Output = V;
+
+    #[inline(always)]
+    #[track_caller]
+    fn index(&self, id: Id<K>) -> &V {
+        self.get(id).expect("no value for Id")
+    }
+}
+
+impl<V, K> IndexMut<Id<K>> for IdMap<V, K> {
+    #[inline(always)]
+    #[track_caller]
+    fn index_mut(&mut self, id: Id<K>) -> &mut V {
+        self.get_mut(id).expect("no value for Id")
+    }
+}
+
+impl<V, K> FromIterator<(Id<K>, V)> for IdMap<V, K> {
+    fn from_iter<T: IntoIterator<Item = (Id<K>, V)>>(iter: T) -> Self {
+        let mut map = Self::new();
+        map.extend(iter);
+        map
+    }
+}
+
+impl<V, K> Extend<(Id<K>, V)> for IdMap<V, K> {
+    fn extend<T: IntoIterator<Item = (Id<K>, V)>>(&mut self, iter: T) {
+        for (id, value) in iter {
+            self.insert(id, value);
+        }
+    }
+}
+
+pub enum Entry<'a, V, K = ()> {
+    Occupied(OccupiedEntry<'a, V, K>),
+    Vacant(VacantEntry<'a, V, K>),
+}
+
+impl<'a, V, K> Entry<'a, V, K> {
+    #[inline(always)]
+    pub fn key(&self) -> Id<K> {
+        match self {
+            Entry::Occupied(entry) => entry.id,
+            Entry::Vacant(entry) => entry.id,
+        }
+    }
+
+    #[inline(always)]
+    pub fn or_insert(self, default: V) -> &'a mut V {
+        self.or_insert_with(|| default)
+    }
+
+    #[inline(always)]
+    pub fn or_insert_with(self, default: impl FnOnce() -> V) -> &'a mut V {
+        match self {
+            Entry::Occupied(entry) => entry.into_mut(),
+            Entry::Vacant(entry) => entry.insert(default()),
+        }
+    }
+
+    #[inline(always)]
+    pub fn or_default(self) -> &'a mut V
+    where
+        V: Default,
+    {
+        self.or_insert_with(V::default)
+    }
+
+    #[inline(always)]
+    pub fn and_modify(mut self, f: impl FnOnce(&mut V)) -> Self {
+        if let Entry::Occupied(entry) = &mut self {
+            f(entry.get_mut());
+        }
+
+        self
+    }
+}
+
+pub struct OccupiedEntry<'a, V, K = ()> {
+    id: Id<K>,
+    slot: &'a mut Option<V>,
+    len: &'a mut usize,
+}
+
+impl<'a, V, K> OccupiedEntry<'a, V, K> {
+    #[inline(always)]
+    pub fn key(&self) -> Id<K> {
+        self.id
+    }
+
+    #[inline(always)]
+    pub fn get(&self) -> &V {
+        unsafe { self.slot.as_ref().unwrap_unchecked() }
+    }
+
+    #[inline(always)]
+    pub fn get_mut(&mut self) -> &mut V {
+        unsafe { self.slot.as_mut().unwrap_unchecked() }
+    }
+
+    #[inline(always)]
+    pub fn into_mut(self) -> &'a mut V {
+        unsafe { self.slot.as_mut().unwrap_unchecked() }
+    }
+
+    #[inline(always)]
+    pub fn insert(&mut self, value: V) -> V {
+        mem::replace(self.get_mut(), value)
+    }
+
+    #[inline(always)]
+    pub fn remove(self) -> V {
+        *self.len -= 1;
+        unsafe { self.slot.take().unwrap_unchecked() }
+    }
+}
+
+pub struct VacantEntry<'a, V, K = ()> {
+    id: Id<K>,
+    slot: &'a mut Option<V>,
+    len: &'a mut usize,
+}
+
+impl<'a, V, K> VacantEntry<'a, V, K> {
+    #[inline(always)]
+    pub fn key(&self) -> Id<K> {
+        self.id
+    }
+
+    #[inline(always)]
+    pub fn insert(self, value: V) -> &'a mut V {
+        *self.len += 1;
+        self.slot.insert(value)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::interner::Interner;
+
+    use super::*;
+
+    #[test]
+    fn dense_values() {
+        let mut interner = Interner::new();
+        let mut lengths = IdVec::new();
+
+        for word in ["a", "bb", "a", "ccc"] {
+            let id = interner.insert(word);
+
+            if id.index() == lengths.len() {
+                lengths.push(word.len());
+            }
+        }
+
+        let a = interner.get("a").unwrap();
+
+        assert_eq!(lengths[a], 1);
+        assert_eq!(lengths.len(), interner.len());
+        assert_eq!(
+            lengths
+                .iter()
+                .map(|(id, len)| (id, *len))
+                .collect::<Vec<_>>(),
+            interner
+                .iter()
+                .map(|(id, string)| (id, string.len()))
+                .collect::<Vec<_>>()
+        );
+    }
+
+    #[test]
+    fn sparse_values() {
+        let mut interner = Interner::new();
+
+        let a = interner.insert("a");
+        let b = interner.insert("b");
+        let c = interner.insert("c");
+
+        let mut map = IdMap::new();
+
+        assert_eq!(map.insert(c, 3), None);
+        assert_eq!(map.insert(a, 1), None);
+        assert_eq!(map.insert(a, 2), Some(1));
+
+        assert_eq!(map.len(), 2);
+        assert_eq!(map.get(b), None);
+        assert_eq!(map[a], 2);
+
+        *map.entry(b).or_insert(0) += 5;
+        *map.entry(b).or_insert(0) += 5;
+        map.entry(c).and_modify(|value| *value *= 10);
+
+        assert_eq!(
+            map.iter()
+                .map(|(id, value)| (id, *value))
+                .collect::<Vec<_>>(),
+            [(a, 2), (b, 10), (c, 30)]
+        );
+
+        assert_eq!(map.remove(b), Some(10));
+        assert_eq!(map.remove(b), None);
+        assert_eq!(map.len(), 2);
+    }
+}
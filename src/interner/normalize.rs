@@ -0,0 +1,273 @@
+use std::borrow::Cow;
+
+#[cfg(not(miri))]
+use gxhash::{HashMap, HashMapExt};
+#[cfg(miri)]
+use std::collections::HashMap;
+
+use super::{Id, Interner, Iter};
+
+mod tables;
+
+/// Maps every spelling of a symbol to a single key
+pub trait Normalize {
+    fn normalize<'a>(&self, string: &'a str) -> Cow<'a, str>;
+}
+
+impl<F: for<'a> Fn(&'a str) -> Cow<'a, str>> Normalize for F {
+    #[inline(always)]
+    fn normalize<'a>(&self, string: &'a str) -> Cow<'a, str> {
+        self(string)
+    }
+}
+
+/// Folds `A-Z` to `a-z`, leaving every other char untouched
+#[derive(Default, Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub struct AsciiCaseFold;
+impl Normalize for AsciiCaseFold {
+    #[inline(always)]
+    fn normalize<'a>(&self, string: &'a str) -> Cow<'a, str> {
+        if string.bytes().any(|b| b.is_ascii_uppercase()) {
+            Cow::Owned(string.to_ascii_lowercase())
+        } else {
+            Cow::Borrowed(string)
+        }
+    }
+}
+
+/// Applies full Unicode case folding, so `ß`, `SS` and `ss` or `ς`, `Σ` and `σ` share a key
+///
+/// # Note
+/// The folding is the default one from CaseFolding.txt, without the Turkic mappings of `I` and `İ`.
+/// It does not normalize, so precomposed and decomposed spellings still differ.
+#[derive(Default, Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub struct UnicodeCaseFold;
+
+impl UnicodeCaseFold {
+    #[inline(always)]
+    fn fold(c: char) -> Option<&'static str> {
+        if c.is_ascii() && !c.is_ascii_uppercase() {
+            return None;
+        }
+
+        tables::CASE_FOLDING
+            .binary_search_by_key(&c, |(c, _)| *c)
+            .ok()
+            .map(|i| tables::CASE_FOLDING[i].1)
+    }
+}
+
+impl Normalize for UnicodeCaseFold {
+    fn normalize<'a>(&self, string: &'a str) -> Cow<'a, str> {
+        let Some(start) = string.find(|c| Self::fold(c).is_some()) else {
+            return Cow::Borrowed(string);
+        };
+
+        let mut folded = String::with_capacity(string.len());
+        folded.push_str(&string[..start]);
+
+        for c in string[start..].chars() {
+            match Self::fold(c) {
+                Some(fold) => folded.push_str(fold),
+                None => folded.push(c),
+            }
+        }
+
+        Cow::Owned(folded)
+    }
+}
+
+/// An `Interner` that gives every spelling with the same normalized key the same `Id`,
+/// while remembering each original spelling for diagnostics.
+pub struct NormalizingInterner<N: Normalize> {
+    normalizer: N,
+    keys: Interner,
+    spellings: Interner,
+    // The first spelling seen for every key, indexed by key `Id`
+    first: Vec<Id>,
+    // Spellings seen after the first, only for keys that have them
+    variants: HashMap<Id, Vec<Id>>,
+}
+
+impl<N: Normalize + Default> Default for NormalizingInterner<N> {
+    #[inline(always)]
+    fn default() -> Self {
+        Self::new(N::default())
+    }
+}
+
+impl<N: Normalize> NormalizingInterner<N> {
+    pub fn new(normalizer: N) -> Self {
+        Self {
+            normalizer,
+            keys: Interner::new(),
+            spellings: Interner::new(),
+            first: Vec::new(),
+            variants: HashMap::new(),
+        }
+    }
+
+    #[inline(always)]
+    pub const fn normalizer(&self) -> &N {
+        &self.normalizer
+    }
+
+    pub fn insert(&mut self, string: &str) -> Id {
+        let id = self.keys.insert(&self.normalizer.normalize(string));
+
+        if id.index() == self.first.len() {
+            self.first.push(self.spellings.insert(string));
+            return id;
+        }
+
+        let len = self.spellings.len();
+        let spelling = self.spellings.insert(string);
+
+        // A spelling always normalizes to the same key, so a new spelling is a new variant
+        if self.spellings.len() != len {
+            self.variants.entry(id).or_default().push(spelling);
+        }
+
+        id
+    }
+
+    #[inline(always)]
+    pub fn get(&self, string: &str) -> Option<Id> {
+        self.keys.get(&self.normalizer.normalize(string))
+    }
+
+    #[inline(always)]
+    pub fn contains(&self, string: &str) -> bool {
+        self.keys.contains(&self.normalizer.normalize(string))
+    }
+
+    /// The normalized key of `id`
+    #[inline(always)]
+    #[track_caller]
+    pub fn resolve(&self, id: Id) -> &str {
+        self.keys.resolve(id)
+    }
+
+    /// The first spelling of `id` that was inserted
+    #[inline(always)]
+    #[track_caller]
+    pub fn spelling(&self, id: Id) -> &str {
+        self.keys.brand.check(id.brand);
+        self.spellings.resolve(self.first[id.index()])
+    }
+
+    /// Every distinct spelling of `id` in the order they were first inserted
+    #[track_caller]
+    pub fn spellings(&self, id: Id) -> impl Iterator<Item = &str> {
+        self.keys.brand.check(id.brand);
+
+        let variants = self
+            .variants
+            .get(&id)
+            .map(Vec::as_slice)
+            .unwrap_or_default();
+
+        [self.first[id.index()]]
+            .into_iter()
+            .chain(variants.iter().copied())
+            .map(|spelling| self.spellings.resolve(spelling))
+    }
+
+    #[inline(always)]
+    pub fn len(&self) -> usize {
+        self.keys.len()
+    }
+
+    #[inline(always)]
+    pub fn is_empty(&self) -> bool {
+        self.keys.is_empty()
+    }
+
+    /// Iterates over every normalized key in `Id` order
+    #[inline(always)]
+    pub fn iter(&self) -> Iter<'_> {
+        self.keys.iter()
+    }
+}
+
+impl<'a, N: Normalize> IntoIterator for &'a NormalizingInterner<N> {
+    type Item = (Id, &'a str);
+    type IntoIter = Iter<'a>;
+
+    #[inline(always)]
+    fn into_iter(self) -> Iter<'a> {
+        self.iter()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn ascii_case_insensitive() {
+        let mut interner = NormalizingInterner::new(AsciiCaseFold);
+
+        let select = interner.insert("SELECT");
+
+        assert_eq!(interner.insert("select"), select);
+        assert_eq!(interner.insert("Select"), select);
+        assert_eq!(interner.insert("SELECT"), select);
+        assert_eq!(interner.get("sElEcT"), Some(select));
+        assert_eq!(interner.len(), 1);
+
+        assert_eq!(interner.resolve(select), "select");
+        assert_eq!(interner.spelling(select), "SELECT");
+        assert_eq!(
+            interner.spellings(select).collect::<Vec<_>>(),
+            ["SELECT", "select", "Select"]
+        );
+    }
+
+    #[test]
+    fn unicode_case_insensitive() {
+        let mut interner = NormalizingInterner::<UnicodeCaseFold>::default();
+
+        let a = interner.insert("ÄPFEL");
+
+        assert_eq!(interner.insert("äpfel"), a);
+        assert_eq!(interner.resolve(a), "äpfel");
+        assert_eq!(interner.spelling(a), "ÄPFEL");
+
+        let strasse = interner.insert("straße");
+
+        assert_eq!(interner.insert("STRASSE"), strasse);
+        assert_eq!(interner.insert("Strasse"), strasse);
+        assert_eq!(interner.resolve(strasse), "strasse");
+
+        let odos = interner.insert("ΟΔΟΣ");
+
+        assert_eq!(interner.insert("οδοσ"), odos);
+        assert_eq!(interner.insert("οδος"), odos);
+        assert_eq!(interner.resolve(odos), "οδοσ");
+        assert!(matches!(
+            UnicodeCaseFold.normalize("straße_1"),
+            Cow::Owned(_)
+        ));
+        assert!(matches!(
+            UnicodeCaseFold.normalize("οδοσ"),
+            Cow::Borrowed(_)
+        ));
+    }
+
+    #[test]
+    fn custom_normalizer() {
+        fn strip_separators(string: &str) -> Cow<'_, str> {
+            Cow::Owned(string.replace(['_', '-'], ""))
+        }
+
+        let mut interner = NormalizingInterner::new(strip_separators);
+
+        let a = interner.insert("max_value");
+
+        assert_eq!(interner.insert("max-value"), a);
+        assert_eq!(interner.insert("maxvalue"), a);
+        assert_ne!(interner.insert("min_value"), a);
+        assert_eq!(interner.spellings(a).count(), 3);
+    }
+}
@@ -0,0 +1,161 @@
+use super::{Id, IdMap};
+
+/// Name resolution on top of `IdMap`, where every binding belongs to a scope
+/// and inner bindings shadow outer ones until their scope is popped.
+pub struct ScopedSymbolTable<V, K = ()> {
+    // Every binding of an id, innermost last, with the depth it was bound at
+    bindings: IdMap<Vec<(usize, V)>, K>,
+    // The ids bound in each scope, outermost first
+    scopes: Vec<Vec<Id<K>>>,
+}
+
+impl<V, K> Default for ScopedSymbolTable<V, K> {
+    #[inline(always)]
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+impl<V, K> ScopedSymbolTable<V, K> {
+    /// Creates a table with a single outermost scope
+    #[inline(always)]
+    pub fn new() -> Self {
+        Self {
+            bindings: IdMap::new(),
+            scopes: vec![Vec::new()],
+        }
+    }
+
+    /// The number of scopes pushed on top of the outermost scope
+    #[inline(always)]
+    pub fn depth(&self) -> usize {
+        self.scopes.len() - 1
+    }
+
+    #[inline(always)]
+    pub fn push_scope(&mut self) {
+        self.scopes.push(Vec::new());
+    }
+
+    /// Removes every binding made since the matching `push_scope`
+    ///
+    /// # Panics
+    /// If only the outermost scope remains
+    #[track_caller]
+    pub fn pop_scope(&mut self) {
+        self.pop_scope_with(|_, _| {});
+    }
+
+    /// Removes every binding made since the matching `push_scope`,
+    /// passing them to `f` from the most recent binding to the least recent.
+    ///
+    /// # Panics
+    /// If only the outermost scope remains
+    #[track_caller]
+    pub fn pop_scope_with(&mut self, mut f: impl FnMut(Id<K>, V)) {
+        assert!(self.scopes.len() > 1, "cannot pop the outermost scope");
+
+        let scope = self.scopes.pop().unwrap();
+
+        for id in scope.into_iter().rev() {
+            let stack = self.bindings.get_mut(id).unwrap();
+            let (_, value) = stack.pop().unwrap();
+
+            if stack.is_empty() {
+                self.bindings.remove(id);
+            }
+
+            f(id, value);
+        }
+    }
+
+    /// Binds `id` in the innermost scope, shadowing every earlier binding
+    pub fn insert(&mut self, id: Id<K>, value: V) {
+        let depth = self.depth();
+
+        self.bindings.entry(id).or_default().push((depth, value));
+        self.scopes.last_mut().unwrap().push(id);
+    }
+
+    /// The innermost binding of `id`
+    #[inline(always)]
+    pub fn get(&self, id: Id<K>) -> Option<&V> {
+        self.get_with_depth(id).map(|(_, value)| value)
+    }
+
+    #[inline(always)]
+    pub fn get_mut(&mut self, id: Id<K>) -> Option<&mut V> {
+        self.bindings
+            .get_mut(id)?
+            .last_mut()
+            .map(|(_, value)| value)
+    }
+
+    /// The innermost binding of `id` with the depth of the scope it was bound in
+    #[inline(always)]
+    pub fn get_with_depth(&self, id: Id<K>) -> Option<(usize, &V)> {
+        self.bindings
+            .get(id)?
+            .last()
+            .map(|(depth, value)| (*depth, value))
+    }
+
+    /// The binding of `id` if it was bound in the innermost scope,
+    /// useful for reporting redeclarations
+    #[inline(always)]
+    pub fn get_in_current_scope(&self, id: Id<K>) -> Option<&V> {
+        self.get_with_depth(id)
+            .filter(|(depth, _)| *depth == self.depth())
+            .map(|(_, value)| value)
+    }
+
+    #[inline(always)]
+    pub fn contains(&self, id: Id<K>) -> bool {
+        self.bindings.contains_key(id)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::interner::Interner;
+
+    use super::*;
+
+    #[test]
+    fn shadowing() {
+        let mut interner = Interner::new();
+
+        let x = interner.insert("x");
+        let y = interner.insert("y");
+
+        let mut table = ScopedSymbolTable::new();
+
+        table.insert(x, "outer x");
+
+        table.push_scope();
+        table.insert(x, "inner x");
+        table.insert(y, "inner y");
+
+        assert_eq!(table.get(x), Some(&"inner x"));
+        assert_eq!(table.get_with_depth(y), Some((1, &"inner y")));
+        assert_eq!(table.get_in_current_scope(x), Some(&"inner x"));
+
+        let mut popped = Vec::new();
+        table.pop_scope_with(|id, value| popped.push((id, value)));
+
+        assert_eq!(popped, [(y, "inner y"), (x, "inner x")]);
+        assert_eq!(table.get(x), Some(&"outer x"));
+        assert_eq!(table.get(y), None);
+        assert!(!table.contains(y));
+        assert_eq!(table.depth(), 0);
+
+        table.push_scope();
+        assert_eq!(table.get_in_current_scope(x), None);
+    }
+
+    #[test]
+    #[should_panic(expected = "cannot pop the outermost scope")]
+    fn pop_outermost() {
+        ScopedSymbolTable::<()>::new().pop_scope();
+    }
+}
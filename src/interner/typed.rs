@@ -0,0 +1,202 @@
+use std::marker::PhantomData;
+
+use super::{Checkpoint, DecodeError, Id, Interner, Iter, Keyword, MemoryUsage};
+
+/// An `Interner` producing `Id<T>`
+///
+/// # Note
+/// `T` is only a marker, use a separate type for each kind of symbol
+/// (such as identifiers and string literals) so their ids cannot be mixed up.
+pub struct TypedInterner<T> {
+    inner: Interner,
+    _marker: PhantomData<fn() -> T>,
+}
+
+impl<T> Default for TypedInterner<T> {
+    #[inline(always)]
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+impl<T> TypedInterner<T> {
+    #[inline(always)]
+    pub fn new() -> Self {
+        Self::from_untyped(Interner::new())
+    }
+
+    #[inline(always)]
+    pub fn with_capacity(strings: usize, bytes: usize) -> Self {
+        Self::from_untyped(Interner::with_capacity(strings, bytes))
+    }
+
+    #[inline(always)]
+    pub fn with_keywords(keywords: &[&str]) -> Self {
+        Self::from_untyped(Interner::with_keywords(keywords))
+    }
+
+    #[inline(always)]
+    pub fn with_keyword_table<K: Keyword>() -> Self {
+        Self::from_untyped(Interner::with_keyword_table::<K>())
+    }
+
+    #[inline(always)]
+    pub const fn from_untyped(inner: Interner) -> Self {
+        Self {
+            inner,
+            _marker: PhantomData,
+        }
+    }
+
+    #[inline(always)]
+    pub fn into_untyped(self) -> Interner {
+        self.inner
+    }
+
+    #[inline(always)]
+    pub fn insert(&mut self, string: &str) -> Id<T> {
+        cast(self.inner.insert(string))
+    }
+
+    #[inline(always)]
+    pub fn get(&self, string: &str) -> Option<Id<T>> {
+        self.inner.get(string).map(cast)
+    }
+
+    #[inline(always)]
+    pub fn contains(&self, string: &str) -> bool {
+        self.inner.contains(string)
+    }
+
+    #[inline(always)]
+    #[track_caller]
+    pub fn resolve(&self, id: Id<T>) -> &str {
+        self.inner.resolve(cast(id))
+    }
+
+    /// # Safety
+    /// `id` must be less than `self.len()`,
+    /// this holds for every `Id` returned by this `TypedInterner`
+    #[inline(always)]
+    #[track_caller]
+    pub unsafe fn resolve_unchecked(&self, id: Id<T>) -> &str {
+        unsafe { self.inner.resolve_unchecked(cast(id)) }
+    }
+
+    #[inline(always)]
+    pub const fn is_keyword(&self, id: Id<T>) -> bool {
+        self.inner.is_keyword(cast(id))
+    }
+
+    #[inline(always)]
+    pub fn keyword_kind<K: Keyword>(&self, id: Id<T>) -> Option<K> {
+        self.inner.keyword_kind(cast(id))
+    }
+
+    #[inline(always)]
+    pub fn len(&self) -> usize {
+        self.inner.len()
+    }
+
+    #[inline(always)]
+    pub fn is_empty(&self) -> bool {
+        self.inner.is_empty()
+    }
+
+    #[inline(always)]
+    pub fn checkpoint(&self) -> Checkpoint {
+        self.inner.checkpoint()
+    }
+
+    #[inline(always)]
+    #[track_caller]
+    pub fn truncate_to(&mut self, checkpoint: Checkpoint) {
+        self.inner.truncate_to(checkpoint)
+    }
+
+    #[inline(always)]
+    pub fn iter(&self) -> Iter<'_, T> {
+        Iter {
+            inner: self.inner.data.iter().enumerate(),
+            brand: self.inner.brand,
+            _marker: PhantomData,
+        }
+    }
+
+    #[inline(always)]
+    pub fn memory_usage(&self) -> MemoryUsage {
+        self.inner.memory_usage()
+    }
+
+    #[inline(always)]
+    pub fn encode(&self, buf: &mut Vec<u8>) {
+        self.inner.encode(buf)
+    }
+
+    #[inline(always)]
+    pub fn to_bytes(&self) -> Vec<u8> {
+        self.inner.to_bytes()
+    }
+
+    #[inline(always)]
+    pub fn from_bytes(bytes: &[u8]) -> Result<Self, DecodeError> {
+        Interner::from_bytes(bytes).map(Self::from_untyped)
+    }
+}
+
+impl<'a, T> IntoIterator for &'a TypedInterner<T> {
+    type Item = (Id<T>, &'a str);
+    type IntoIter = Iter<'a, T>;
+
+    #[inline(always)]
+    fn into_iter(self) -> Iter<'a, T> {
+        self.iter()
+    }
+}
+
+#[cfg(feature = "serde")]
+impl<T> serde::Serialize for TypedInterner<T> {
+    #[inline(always)]
+    fn serialize<S: serde::Serializer>(&self, serializer: S) -> Result<S::Ok, S::Error> {
+        self.inner.serialize(serializer)
+    }
+}
+
+#[cfg(feature = "serde")]
+impl<'de, T> serde::Deserialize<'de> for TypedInterner<T> {
+    #[inline(always)]
+    fn deserialize<D: serde::Deserializer<'de>>(deserializer: D) -> Result<Self, D::Error> {
+        Interner::deserialize(deserializer).map(Self::from_untyped)
+    }
+}
+
+#[inline(always)]
+const fn cast<T, U>(id: Id<T>) -> Id<U> {
+    Id {
+        raw: id.raw,
+        brand: id.brand,
+        _marker: PhantomData,
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    struct Ident;
+    struct Literal;
+
+    #[test]
+    fn typed_ids() {
+        let mut idents = TypedInterner::<Ident>::new();
+        let mut literals = TypedInterner::<Literal>::new();
+
+        let a: Id<Ident> = idents.insert("a");
+        let b: Id<Literal> = literals.insert("b");
+
+        assert_eq!(idents.resolve(a), "a");
+        assert_eq!(literals.resolve(b), "b");
+        assert_eq!(idents.get("a"), Some(a));
+        assert_eq!(idents.iter().collect::<Vec<_>>(), [(a, "a")]);
+    }
+}
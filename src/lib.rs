@@ -1,4 +1,4 @@
-#![feature(never_type, coerce_unsized)]
+#![feature(never_type)]
 
 pub mod input;
 pub mod interner;
@@ -1,11 +1,31 @@
 use crate::input::{Input, ReadError};
 
+mod alt;
+mod lookahead;
+mod parse_indent_iter;
 mod parse_iter;
 mod parse_mut_borrowed_iter;
 mod parse_mut_iter;
+mod parse_trivia_iter;
+mod recover;
+mod repeat;
+mod rich_error;
+mod sequence;
+#[cfg(test)]
+mod testing;
+pub use alt::{Alt, AltError, alt};
+pub use lookahead::{Not, Opt, Peek, Unexpected};
+pub use parse_indent_iter::{IndentError, IndentErrorKind, Layout, ParseIndentIter};
 pub use parse_iter::{ParseIter, ParseIterError};
 pub use parse_mut_borrowed_iter::ParseMutBorrowedIter;
 pub use parse_mut_iter::ParseMutIter;
+pub use parse_trivia_iter::ParseTriviaIter;
+pub use recover::{Recover, SyncChars, SyncParse};
+pub use repeat::{
+    FoldMany, Repeat, SeparatedList, fold_many, many0, many1, repeat, separated_list,
+};
+pub use rich_error::{ContextParse, RichError};
+pub use sequence::{Delimited, Preceded, Terminated, delimited, preceded, terminated};
 
 #[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
 pub enum ParseError<E> {
@@ -36,6 +56,24 @@ impl<E> ParseError<E> {
     }
 }
 
+/// Converts the result of an item of an iterator, `None` at the end of input
+#[inline(always)]
+pub(crate) fn iter_result<O, E>(
+    result: Result<O, ParseError<E>>,
+) -> Result<Option<O>, ParseIterError<E>> {
+    match result {
+        Ok(output) => Ok(Some(output)),
+        Err(ParseError::ReadError(ReadError::EOF)) => Ok(None),
+        Err(ParseError::ReadError(ReadError::InvalidUtf8(err))) => {
+            Err(ParseIterError::InvalidUtf8(err))
+        }
+        Err(ParseError::ReadError(ReadError::TokenTooLong(err))) => {
+            Err(ParseIterError::TokenTooLong(err))
+        }
+        Err(ParseError::Error(err)) => Err(ParseIterError::Error(err)),
+    }
+}
+
 pub trait IsParse<'a> {
     type Output;
     type Error;
@@ -76,9 +114,78 @@ pub trait ParseExt: Parse {
             mapping,
         }
     }
+
+    /// Tries this parser and then `other` if this parser fails without consuming input,
+    /// see `alt`
+    #[inline(always)]
+    fn or<P>(self, other: P) -> Alt<(Self, P)> {
+        alt((self, other))
+    }
+
+    /// Gives `None` instead of failing if this parser fails without consuming input
+    #[inline(always)]
+    fn opt(self) -> Opt<Self> {
+        Opt { parse: self }
+    }
+
+    /// Runs this parser without consuming input
+    #[inline(always)]
+    fn peek(self) -> Peek<Self> {
+        Peek { parse: self }
+    }
+
+    /// Succeeds without consuming input only if this parser fails
+    #[inline(always)]
+    fn not(self) -> Not<Self> {
+        Not { parse: self }
+    }
+
+    /// Converts the error of this parser, such as to share one error type within a sequence
+    #[inline(always)]
+    fn map_err<F: for<'a> FnOnce(ParseErrorOutput<'a, Self>) -> E, E>(
+        self,
+        mapping: F,
+    ) -> MapErrParse<Self, F> {
+        MapErrParse {
+            parse: self,
+            mapping,
+        }
+    }
+
+    /// Converts the error of this parser into a `RichError` within `context`,
+    /// such as `"function body"`
+    #[inline(always)]
+    fn context(self, context: &'static str) -> ContextParse<Self> {
+        ContextParse {
+            parse: self,
+            context,
+        }
+    }
 }
 impl<P: Parse> ParseExt for P {}
 
+#[derive(Debug, Clone, Copy)]
+pub struct MapErrParse<P: Parse, F> {
+    pub parse: P,
+    pub mapping: F,
+}
+
+impl<'a, P: Parse, F: FnOnce(ParseErrorOutput<'a, P>) -> E, E> IsParse<'a> for MapErrParse<P, F> {
+    type Output = ParseOutput<'a, P>;
+    type Error = E;
+
+    #[inline(always)]
+    fn __parse<I: ?Sized + Input>(
+        self,
+        input: &'a mut I,
+    ) -> Result<Self::Output, ParseError<Self::Error>> {
+        self.parse.parse(input).map_err(|err| match err {
+            ParseError::ReadError(err) => ParseError::ReadError(err),
+            ParseError::Error(err) => ParseError::Error((self.mapping)(err)),
+        })
+    }
+}
+
 pub trait IsMapping<'a, P: IsParse<'a>> {
     type Output;
 
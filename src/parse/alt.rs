@@ -0,0 +1,179 @@
+use crate::{
+    input::{Input, ReadError},
+    parse::{IsParse, Parse, ParseError},
+};
+
+/// Tries each parser of `branches` (a tuple) in order and returns the first success
+#[inline(always)]
+pub const fn alt<T>(branches: T) -> Alt<T> {
+    Alt { branches }
+}
+
+/// # Note
+/// Input cannot be rewound, so a branch that fails after consuming input
+/// stops the search with `AltError::Committed` rather than trying the next branch.
+#[derive(Debug, Clone, Copy)]
+pub struct Alt<T> {
+    pub branches: T,
+}
+
+#[derive(Debug, Clone, PartialEq, Eq, Hash)]
+pub enum AltError<E> {
+    /// Every branch failed without consuming input, with the error of each branch in order
+    Expected(Vec<E>),
+    /// The branch at index `branch` failed after consuming input
+    Committed { branch: usize, error: E },
+}
+
+impl<E> AltError<E> {
+    /// The error of every branch that was tried
+    #[inline(always)]
+    pub fn errors(&self) -> &[E] {
+        match self {
+            AltError::Expected(errors) => errors,
+            AltError::Committed { error, .. } => std::slice::from_ref(error),
+        }
+    }
+}
+
+macro_rules! impl_alt {
+    ($($branch:literal $parse:ident $var:ident),+; $next:ident) => {
+        impl<$($parse),+> Alt<($($parse,)+)> {
+            /// Adds another branch, so `a.or(b).or(c)` is the same as `alt((a, b, c))`
+            #[inline(always)]
+            pub fn or<$next>(self, next: $next) -> Alt<($($parse,)+ $next)> {
+                let ($($var,)+) = self.branches;
+
+                Alt {
+                    branches: ($($var,)+ next),
+                }
+            }
+        }
+
+        impl_alt!($($branch $parse $var),+);
+    };
+    ($($branch:literal $parse:ident $var:ident),+) => {
+        impl<'a, O, E, $($parse),+> IsParse<'a> for Alt<($($parse,)+)>
+        where
+            $($parse: for<'s> IsParse<'s, Output = O, Error = E>,)+
+        {
+            type Output = O;
+            type Error = AltError<E>;
+
+            fn __parse<I: ?Sized + Input>(
+                self,
+                input: &'a mut I,
+            ) -> Result<Self::Output, ParseError<Self::Error>> {
+                let ($($var,)+) = self.branches;
+
+                let mut expected = Vec::new();
+
+                $(
+                    let start = input.index();
+
+                    match $var.parse(&mut *input) {
+                        Ok(output) => return Ok(output),
+                        Err(ParseError::Error(error)) => {
+                            if input.index() != start {
+                                return Err(ParseError::Error(AltError::Committed {
+                                    branch: $branch,
+                                    error,
+                                }));
+                            }
+
+                            expected.push(error);
+                        }
+                        // Running out of data for one branch does not mean the next cannot match
+                        Err(ParseError::ReadError(ReadError::EOF))
+                            if !input.is_eof() && input.index() == start => {}
+                        Err(ParseError::ReadError(err)) => return Err(ParseError::ReadError(err)),
+                    }
+                )+
+
+                if expected.is_empty() {
+                    Err(ParseError::ReadError(ReadError::EOF))
+                } else {
+                    Err(ParseError::Error(AltError::Expected(expected)))
+                }
+            }
+        }
+    };
+}
+
+impl_alt!(0 A a, 1 B b; C);
+impl_alt!(0 A a, 1 B b, 2 C c; D);
+impl_alt!(0 A a, 1 B b, 2 C c, 3 D d; E2);
+impl_alt!(0 A a, 1 B b, 2 C c, 3 D d, 4 E2 e; F);
+impl_alt!(0 A a, 1 B b, 2 C c, 3 D d, 4 E2 e, 5 F f; G);
+impl_alt!(0 A a, 1 B b, 2 C c, 3 D d, 4 E2 e, 5 F f, 6 G g; H);
+impl_alt!(0 A a, 1 B b, 2 C c, 3 D d, 4 E2 e, 5 F f, 6 G g, 7 H h; I2);
+impl_alt!(0 A a, 1 B b, 2 C c, 3 D d, 4 E2 e, 5 F f, 6 G g, 7 H h, 8 I2 i; J);
+impl_alt!(0 A a, 1 B b, 2 C c, 3 D d, 4 E2 e, 5 F f, 6 G g, 7 H h, 8 I2 i, 9 J j; K);
+impl_alt!(0 A a, 1 B b, 2 C c, 3 D d, 4 E2 e, 5 F f, 6 G g, 7 H h, 8 I2 i, 9 J j, 10 K k; L);
+impl_alt!(0 A a, 1 B b, 2 C c, 3 D d, 4 E2 e, 5 F f, 6 G g, 7 H h, 8 I2 i, 9 J j, 10 K k, 11 L l);
+
+#[cfg(test)]
+mod tests {
+    use crate::{
+        input::StrView,
+        parse::{
+            ParseExt,
+            testing::{DIGITS, LETTERS, SPACES},
+        },
+    };
+
+    use super::*;
+
+    #[test]
+    fn first_success() {
+        let mut input = StrView::new("abc 123");
+
+        assert_eq!(
+            alt((DIGITS, LETTERS)).parse(&mut input),
+            Ok("abc".to_string())
+        );
+        assert_eq!(
+            DIGITS.or(LETTERS).or(SPACES).parse(&mut input),
+            Ok(" ".to_string())
+        );
+        assert_eq!(LETTERS.or(DIGITS).parse(&mut input), Ok("123".to_string()));
+    }
+
+    #[test]
+    fn aggregated_expected() {
+        let mut input = StrView::new("+");
+
+        assert_eq!(
+            alt((DIGITS, LETTERS, SPACES)).parse(&mut input),
+            Err(ParseError::Error(AltError::Expected(vec![
+                "digits", "letters", "spaces"
+            ])))
+        );
+    }
+
+    #[test]
+    fn committed_branch() {
+        let mut input = StrView::new("abc+");
+
+        let pair = (LETTERS, DIGITS).mapped(|(a, b): (String, String)| a + &b);
+        let letters = LETTERS.mapped(|a: String| a);
+
+        assert_eq!(
+            pair.or(letters).parse(&mut input),
+            Err(ParseError::Error(AltError::Committed {
+                branch: 0,
+                error: "digits"
+            }))
+        );
+    }
+
+    #[test]
+    fn end_of_input() {
+        let mut input = StrView::new("");
+
+        assert_eq!(
+            DIGITS.or(LETTERS).parse(&mut input),
+            Err(ParseError::ReadError(ReadError::EOF))
+        );
+    }
+}
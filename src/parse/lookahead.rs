@@ -0,0 +1,160 @@
+use crate::{
+    input::{Input, Lookahead, ReadError},
+    parse::{IsParse, Parse, ParseError},
+};
+
+/// # Note
+/// Only a failure without consuming input (or the end of input) gives `None`,
+/// a failure after consuming input is still returned as an error.
+#[derive(Debug, Clone, Copy)]
+pub struct Opt<P> {
+    pub parse: P,
+}
+
+impl<'a, P, O, E> IsParse<'a> for Opt<P>
+where
+    P: for<'s> IsParse<'s, Output = O, Error = E>,
+{
+    type Output = Option<O>;
+    type Error = E;
+
+    fn __parse<I: ?Sized + Input>(
+        self,
+        input: &'a mut I,
+    ) -> Result<Self::Output, ParseError<Self::Error>> {
+        let start = input.index();
+
+        match self.parse.parse(&mut *input) {
+            Ok(output) => Ok(Some(output)),
+            Err(ParseError::Error(_)) if input.index() == start => Ok(None),
+            Err(ParseError::ReadError(ReadError::EOF))
+                if input.is_eof() || input.index() == start =>
+            {
+                Ok(None)
+            }
+            Err(err) => Err(err),
+        }
+    }
+}
+
+/// Runs the inner parser on a `Lookahead` so no input is consumed
+#[derive(Debug, Clone, Copy)]
+pub struct Peek<P> {
+    pub parse: P,
+}
+
+impl<'a, P, O, E> IsParse<'a> for Peek<P>
+where
+    P: for<'s> IsParse<'s, Output = O, Error = E>,
+{
+    type Output = O;
+    type Error = E;
+
+    #[inline(always)]
+    fn __parse<I: ?Sized + Input>(
+        self,
+        input: &'a mut I,
+    ) -> Result<Self::Output, ParseError<Self::Error>> {
+        self.parse.parse(&mut Lookahead::new(input))
+    }
+}
+
+/// Succeeds only if the inner parser fails, never consuming input
+///
+/// # Note
+/// The end of input counts as a failure of the inner parser.
+#[derive(Debug, Clone, Copy)]
+pub struct Not<P> {
+    pub parse: P,
+}
+
+/// The inner parser of `Not` succeeded with `found`
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub struct Unexpected<O> {
+    pub found: O,
+}
+
+impl<'a, P, O, E> IsParse<'a> for Not<P>
+where
+    P: for<'s> IsParse<'s, Output = O, Error = E>,
+{
+    type Output = ();
+    type Error = Unexpected<O>;
+
+    fn __parse<I: ?Sized + Input>(
+        self,
+        input: &'a mut I,
+    ) -> Result<Self::Output, ParseError<Self::Error>> {
+        match self.parse.parse(&mut Lookahead::new(input)) {
+            Ok(found) => Err(ParseError::Error(Unexpected { found })),
+            Err(ParseError::Error(_)) | Err(ParseError::ReadError(ReadError::EOF)) => Ok(()),
+            Err(ParseError::ReadError(err)) => Err(ParseError::ReadError(err)),
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::{
+        input::StrView,
+        parse::{
+            ParseExt, terminated,
+            testing::{DIGITS, LETTERS, Run},
+        },
+    };
+
+    use super::*;
+
+    const IF: Run = Run(|c| c == 'i' || c == 'f', "if");
+
+    #[test]
+    fn optional() {
+        let mut input = StrView::new("abc");
+
+        assert_eq!(DIGITS.opt().parse(&mut input), Ok(None));
+        assert_eq!(LETTERS.opt().parse(&mut input), Ok(Some("abc".to_string())));
+        assert_eq!(LETTERS.opt().parse(&mut input), Ok(None));
+    }
+
+    #[test]
+    fn peek_does_not_consume() {
+        let mut input = StrView::new("abc1");
+
+        assert_eq!(LETTERS.peek().parse(&mut input), Ok("abc".to_string()));
+        assert_eq!(
+            DIGITS.peek().parse(&mut input),
+            Err(ParseError::Error("digits"))
+        );
+        assert_eq!(LETTERS.parse(&mut input), Ok("abc".to_string()));
+        assert_eq!(DIGITS.peek().parse(&mut input), Ok("1".to_string()));
+        assert_eq!(input.read(), "1");
+    }
+
+    #[test]
+    fn keyword_boundary() {
+        let keyword = || terminated(IF, DIGITS.not().map_err(|_| "boundary"));
+
+        assert_eq!(
+            keyword().parse(&mut StrView::new("if x")),
+            Ok("if".to_string())
+        );
+        assert_eq!(
+            keyword().parse(&mut StrView::new("if")),
+            Ok("if".to_string())
+        );
+
+        let mut input = StrView::new("if2");
+        assert_eq!(
+            keyword().parse(&mut input),
+            Err(ParseError::Error("boundary"))
+        );
+        assert_eq!(input.read(), "2");
+
+        assert_eq!(
+            LETTERS.not().parse(&mut StrView::new("abc")),
+            Err(ParseError::Error(Unexpected {
+                found: "abc".to_string()
+            }))
+        );
+    }
+}
@@ -0,0 +1,379 @@
+use std::collections::VecDeque;
+
+use token_precedence::span::Span;
+
+use crate::{
+    input::{self, Input, InputExt, ReadError},
+    parse::{IsParse, Parse, ParseError, ParseIterError, RichError, iter_result},
+};
+
+/// An item of a `ParseIndentIter`
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub enum Layout<O> {
+    Item(O),
+    /// The end of a line with items
+    Newline(Span),
+    /// The start of a line indented further than the last
+    Indent(Span),
+    /// The start of a line indented less than the last, once per closed level
+    Dedent(Span),
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub enum IndentErrorKind {
+    /// The indentation mixes tabs and spaces differently to the enclosing level
+    Inconsistent,
+    /// The indentation is less than the last but matches no enclosing level
+    Unaligned,
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub struct IndentError {
+    pub kind: IndentErrorKind,
+    pub span: Span,
+}
+
+/// Parses items separated by spaces and tabs, emitting `Layout` tokens for lines and indentation
+///
+/// # Note
+/// Lines with nothing but whitespace are skipped.
+/// Levels still open at the end of input are closed by a `Dedent` each.
+/// The iteration continues after an `IndentError`, but ends after any other error.
+#[derive(Debug)]
+pub struct ParseIndentIter<'a, I: ?Sized + Input, P> {
+    input: &'a mut I,
+    parser: P,
+    /// The indentation of each open level, starting with the unindented level
+    levels: Vec<String>,
+    pending: VecDeque<Layout<!>>,
+    line_start: bool,
+    line_has_items: bool,
+    done: bool,
+}
+
+impl<'a, I: ?Sized + Input, P> ParseIndentIter<'a, I, P> {
+    #[inline(always)]
+    pub fn new(input: &'a mut I, parser: P) -> Self {
+        Self {
+            input,
+            parser,
+            levels: vec![String::new()],
+            pending: VecDeque::new(),
+            line_start: true,
+            line_has_items: false,
+            done: false,
+        }
+    }
+
+    /// Closes the last line and every open level at `index`
+    fn end(&mut self, index: usize) {
+        let span = Span::new(index, index);
+
+        if self.line_has_items {
+            self.pending.push_back(Layout::Newline(span));
+        }
+
+        for _ in 1..self.levels.len() {
+            self.pending.push_back(Layout::Dedent(span));
+        }
+
+        self.levels.truncate(1);
+        self.done = true;
+    }
+
+    /// Compares the indentation of a line to the open levels
+    fn indent(&mut self, indent: &str, span: Span) -> Result<(), IndentError> {
+        let error = |kind| IndentError { kind, span };
+
+        let last = self
+            .levels
+            .last()
+            .expect("the unindented level is never closed");
+
+        if indent == last {
+            return Ok(());
+        }
+
+        if indent.starts_with(last.as_str()) {
+            self.levels.push(indent.to_string());
+            self.pending.push_back(Layout::Indent(span));
+            return Ok(());
+        }
+
+        if !last.starts_with(indent) {
+            return Err(error(IndentErrorKind::Inconsistent));
+        }
+
+        let Some(level) = self.levels.iter().rposition(|level| level == indent) else {
+            return Err(error(IndentErrorKind::Unaligned));
+        };
+
+        for _ in level + 1..self.levels.len() {
+            self.pending.push_back(Layout::Dedent(span));
+        }
+
+        self.levels.truncate(level + 1);
+        Ok(())
+    }
+
+    /// Skips blank lines and measures the indentation of the next line
+    fn start_line<E: From<IndentError>>(&mut self) -> Result<(), ParseError<E>> {
+        loop {
+            let start = self.input.index();
+
+            let entry = match self
+                .input
+                .read_until_entry(16, |c| !matches!(c, ' ' | '\t' | '\r'))
+            {
+                Ok(entry) => entry,
+                Err(ReadError::EOF) => {
+                    self.end(start);
+                    return Ok(());
+                }
+                Err(err) => return Err(ParseError::ReadError(err)),
+            };
+
+            let indent = entry.get().to_string();
+            let span = entry.span();
+            entry.consume();
+
+            match self.input.peek_entry() {
+                Ok(entry) if entry.get() == '\n' => entry.consume(),
+                Ok(_) => {
+                    self.line_start = false;
+
+                    return self
+                        .indent(&indent, span)
+                        .map_err(|err| ParseError::Error(err.into()));
+                }
+                Err(ReadError::EOF) => {
+                    self.end(start + indent.len());
+                    return Ok(());
+                }
+                Err(err) => return Err(ParseError::ReadError(err)),
+            }
+        }
+    }
+
+    /// Skips spaces and tabs, ending the line at a newline
+    fn skip_inline(&mut self) -> input::Result<()> {
+        let start = self.input.index();
+
+        match self
+            .input
+            .consume_until(8, |c| !matches!(c, ' ' | '\t' | '\r'))
+        {
+            Ok(()) => {}
+            Err(ReadError::EOF) => {
+                self.end(start);
+                return Ok(());
+            }
+            Err(err) => return Err(err),
+        }
+
+        let index = self.input.index();
+
+        match self.input.peek_entry() {
+            Ok(entry) if entry.get() == '\n' => {
+                let span = entry.span();
+                entry.consume();
+
+                self.line_start = true;
+
+                if self.line_has_items {
+                    self.line_has_items = false;
+                    self.pending.push_back(Layout::Newline(span));
+                }
+            }
+            Ok(_) => {}
+            Err(ReadError::EOF) => self.end(index),
+            Err(err) => return Err(err),
+        }
+
+        Ok(())
+    }
+}
+
+impl<'a, I: ?Sized + Input, P, O, E> Iterator for ParseIndentIter<'a, I, P>
+where
+    P: for<'s> IsParse<'s, Output = O, Error = E> + Clone,
+    E: From<IndentError>,
+{
+    type Item = Result<Layout<O>, ParseIterError<E>>;
+
+    fn next(&mut self) -> Option<Result<Layout<O>, ParseIterError<E>>> {
+        loop {
+            if let Some(layout) = self.pending.pop_front() {
+                return Some(Ok(match layout {
+                    Layout::Newline(span) => Layout::Newline(span),
+                    Layout::Indent(span) => Layout::Indent(span),
+                    Layout::Dedent(span) => Layout::Dedent(span),
+                }));
+            }
+
+            if self.done {
+                return None;
+            }
+
+            let result = if self.line_start {
+                self.start_line()
+            } else {
+                self.skip_inline().map_err(ParseError::ReadError)
+            };
+
+            match iter_result(result) {
+                Ok(Some(())) => {}
+                Ok(None) => {
+                    self.end(self.input.index());
+                    continue;
+                }
+                // The line is left behind, so the iteration continues after an indentation error
+                Err(ParseIterError::Error(err)) => return Some(Err(ParseIterError::Error(err))),
+                Err(err) => {
+                    self.done = true;
+                    return Some(Err(err));
+                }
+            }
+
+            if self.line_start || self.done || !self.pending.is_empty() {
+                continue;
+            }
+
+            return match iter_result(self.parser.clone().parse(&mut *self.input)) {
+                Ok(Some(output)) => {
+                    self.line_has_items = true;
+                    Some(Ok(Layout::Item(output)))
+                }
+                Ok(None) => {
+                    self.end(self.input.index());
+                    continue;
+                }
+                // The parser may not have consumed anything, so it would fail the same way again
+                Err(err) => {
+                    self.done = true;
+                    Some(Err(err))
+                }
+            };
+        }
+    }
+}
+
+impl From<IndentError> for RichError {
+    #[inline(always)]
+    fn from(value: IndentError) -> Self {
+        RichError::new(value.span).expecting(match value.kind {
+            IndentErrorKind::Inconsistent => "consistent indentation",
+            IndentErrorKind::Unaligned => "indentation of an enclosing level",
+        })
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::{core::parse::Tag, input::StrView, parse::ParseExt};
+
+    use super::*;
+
+    #[derive(Debug, Clone, Copy, PartialEq, Eq)]
+    enum Error {
+        Tag,
+        Indent(IndentErrorKind),
+    }
+
+    impl From<IndentError> for Error {
+        fn from(err: IndentError) -> Self {
+            Error::Indent(err.kind)
+        }
+    }
+
+    fn layout(s: &str) -> Vec<Result<Layout<()>, ParseIterError<Error>>> {
+        let parser = Tag("a").map_err(|_| Error::Tag).mapped(drop);
+
+        ParseIndentIter::new(&mut StrView::new(s), parser)
+            .map(|item| {
+                item.map(|layout| match layout {
+                    Layout::Item(()) => Layout::Item(()),
+                    Layout::Newline(_) => Layout::Newline(Span::default()),
+                    Layout::Indent(_) => Layout::Indent(Span::default()),
+                    Layout::Dedent(_) => Layout::Dedent(Span::default()),
+                })
+            })
+            .collect()
+    }
+
+    #[test]
+    fn blocks() {
+        const A: Layout<()> = Layout::Item(());
+        const N: Layout<()> = Layout::Newline(Span::new(0, 0));
+        const I: Layout<()> = Layout::Indent(Span::new(0, 0));
+        const D: Layout<()> = Layout::Dedent(Span::new(0, 0));
+
+        let layout = |s| {
+            layout(s)
+                .into_iter()
+                .map(Result::unwrap)
+                .collect::<Vec<_>>()
+        };
+
+        assert_eq!(
+            layout("a a\n  a\n\n    a\n  a\na"),
+            [A, A, N, I, A, N, I, A, N, D, A, N, D, A, N]
+        );
+        assert_eq!(
+            layout("a\n\ta\n\t\ta\n  \n"),
+            [A, N, I, A, N, I, A, N, D, D]
+        );
+        assert_eq!(layout("  \n\n"), []);
+    }
+
+    #[test]
+    fn spans() {
+        let mut input = StrView::new("a\n  a");
+        let parser = Tag("a").map_err(|_| Error::Tag);
+
+        let spans: Vec<_> = ParseIndentIter::new(&mut input, parser)
+            .map(|item| match item.unwrap() {
+                Layout::Item(span)
+                | Layout::Newline(span)
+                | Layout::Indent(span)
+                | Layout::Dedent(span) => span,
+            })
+            .collect();
+
+        assert_eq!(
+            spans,
+            [
+                Span::new(0, 1),
+                Span::new(1, 2),
+                Span::new(2, 4),
+                Span::new(4, 5),
+                Span::new(5, 5),
+                Span::new(5, 5),
+            ]
+        );
+    }
+
+    #[test]
+    fn errors() {
+        let error = |kind| Err(ParseIterError::Error(Error::Indent(kind)));
+
+        assert_eq!(
+            layout("a\n    a\n\ta")[5],
+            error(IndentErrorKind::Inconsistent)
+        );
+        assert_eq!(
+            layout("a\n    a\n  a")[5],
+            error(IndentErrorKind::Unaligned)
+        );
+        assert_eq!(
+            layout("a\n  a b\na"),
+            [
+                Ok(Layout::Item(())),
+                Ok(Layout::Newline(Span::default())),
+                Ok(Layout::Indent(Span::default())),
+                Ok(Layout::Item(())),
+                Err(ParseIterError::Error(Error::Tag))
+            ]
+        );
+    }
+}
@@ -1,12 +1,17 @@
 use crate::{
-    input::{Input, InvalidUtf8, ReadError},
-    parse::{IsParse, Parse, ParseError},
-    trim::Trim,
+    input::{Input, InvalidUtf8, TokenTooLong},
+    parse::{
+        IsParse, Parse,
+        recover::{Recover, Recovery},
+    },
+    trim::TryTrim,
 };
 
 #[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+#[non_exhaustive]
 pub enum ParseIterError<E> {
     InvalidUtf8(InvalidUtf8),
+    TokenTooLong(TokenTooLong),
     Error(E),
 }
 
@@ -17,6 +22,13 @@ impl<E> From<InvalidUtf8> for ParseIterError<E> {
     }
 }
 
+impl<E> From<TokenTooLong> for ParseIterError<E> {
+    #[inline(always)]
+    fn from(value: TokenTooLong) -> Self {
+        ParseIterError::TokenTooLong(value)
+    }
+}
+
 impl<E> ParseIterError<E> {
     #[inline(always)]
     pub const fn new(err: E) -> Self {
@@ -25,56 +37,64 @@ impl<E> ParseIterError<E> {
 }
 
 #[derive(Debug)]
-pub struct ParseIter<'a, I: ?Sized + Input, T: Trim, P: Parse> {
+pub struct ParseIter<'a, I: ?Sized + Input, T, P: Parse, R = ()> {
     input: &'a mut I,
     trimmer: T,
     parser: P,
+    recovery: Recovery<R>,
 }
 
-impl<'a, I: ?Sized + Input, T: Trim + Clone, P: Parse> ParseIter<'a, I, T, P> {
+impl<'a, I: ?Sized + Input, T: TryTrim<E> + Clone, P: for<'s> IsParse<'s, Error = E> + Clone, E>
+    ParseIter<'a, I, T, P>
+{
     #[inline(always)]
-    pub fn new(input: &'a mut I, trimmer: T, parser: P) -> Result<Self, InvalidUtf8> {
-        if let Err(ReadError::InvalidUtf8(err)) = trimmer.clone().trim(input) {
-            return Err(err);
-        }
+    pub fn new(input: &'a mut I, trimmer: T, parser: P) -> Result<Self, ParseIterError<E>> {
+        let recovery = Recovery::start(&mut *input, trimmer.clone())?;
 
         Ok(Self {
             input,
             trimmer,
             parser,
+            recovery,
         })
     }
 }
 
+impl<'a, I: ?Sized + Input, T, P: Parse, R> ParseIter<'a, I, T, P, R> {
+    /// Recovers from errors with `recover` instead of ending the iteration
+    #[inline(always)]
+    pub fn recover<R2: Recover + Clone>(self, recover: R2) -> ParseIter<'a, I, T, P, R2> {
+        ParseIter {
+            input: self.input,
+            trimmer: self.trimmer,
+            parser: self.parser,
+            recovery: self.recovery.with_recover(recover),
+        }
+    }
+
+    /// Ends the iteration after `max_errors` errors in a row
+    #[inline(always)]
+    pub fn max_errors(mut self, max_errors: usize) -> Self {
+        self.recovery.max_errors = max_errors;
+        self
+    }
+}
+
 impl<
     'a,
     I: ?Sized + Input,
-    T: Trim + Clone,
+    T: TryTrim<E> + Clone,
     P: for<'s> IsParse<'s, Output = O, Error = E> + Clone,
     O,
     E,
-> Iterator for ParseIter<'a, I, T, P>
+    R: Recover + Clone,
+> Iterator for ParseIter<'a, I, T, P, R>
 {
     type Item = Result<O, ParseIterError<E>>;
 
     fn next(&mut self) -> Option<Result<O, ParseIterError<E>>> {
-        if self.input.is_eof() {
-            return None;
-        }
-
-        let output = match self.parser.clone().parse(self.input) {
-            Ok(output) => output,
-            Err(ParseError::ReadError(ReadError::EOF)) => return None,
-            Err(ParseError::ReadError(ReadError::InvalidUtf8(err))) => {
-                return Some(Err(ParseIterError::InvalidUtf8(err)));
-            }
-            Err(ParseError::Error(err)) => return Some(Err(ParseIterError::Error(err))),
-        };
-
-        if let Err(ReadError::InvalidUtf8(err)) = self.trimmer.clone().trim(self.input) {
-            return Some(Err(ParseIterError::InvalidUtf8(err)));
-        }
-
-        Some(Ok(output))
+        self.recovery.next(self.input, &self.trimmer, |input| {
+            self.parser.clone().parse(input)
+        })
     }
 }
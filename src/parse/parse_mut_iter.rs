@@ -1,61 +1,73 @@
 use crate::{
-    input::{Input, InvalidUtf8, ReadError},
-    parse::{IsParse, Parse, ParseError, ParseIterError},
-    trim::Trim,
+    input::Input,
+    parse::{
+        IsParse, Parse, ParseIterError,
+        recover::{Recover, Recovery},
+    },
+    trim::TryTrim,
 };
 
 #[derive(Debug)]
-pub struct ParseMutIter<'a, I: ?Sized + Input, T: Trim, P>
+pub struct ParseMutIter<'a, I: ?Sized + Input, T, P, R = ()>
 where
     for<'s> &'s mut P: Parse,
 {
     input: &'a mut I,
     trimmer: T,
     parser: P,
+    recovery: Recovery<R>,
 }
 
-impl<'a, I: ?Sized + Input, T: Trim + Clone, P> ParseMutIter<'a, I, T, P>
+impl<'a, I: ?Sized + Input, T: TryTrim<E> + Clone, P, E> ParseMutIter<'a, I, T, P>
 where
     for<'s> &'s mut P: Parse,
+    for<'s, 'k> &'s mut P: IsParse<'k, Error = E>,
 {
     #[inline(always)]
-    pub fn new(input: &'a mut I, trimmer: T, parser: P) -> Result<Self, InvalidUtf8> {
-        if let Err(ReadError::InvalidUtf8(err)) = trimmer.clone().trim(input) {
-            return Err(err);
-        }
+    pub fn new(input: &'a mut I, trimmer: T, parser: P) -> Result<Self, ParseIterError<E>> {
+        let recovery = Recovery::start(&mut *input, trimmer.clone())?;
 
         Ok(Self {
             input,
             trimmer,
             parser,
+            recovery,
         })
     }
 }
 
-impl<'a, I: ?Sized + Input, T: Trim + Clone, P, O, E> Iterator for ParseMutIter<'a, I, T, P>
+impl<'a, I: ?Sized + Input, T, P, R> ParseMutIter<'a, I, T, P, R>
 where
-    for<'s, 'k> &'s mut P: IsParse<'k, Output = O, Error = E>,
+    for<'s> &'s mut P: Parse,
 {
-    type Item = Result<O, ParseIterError<E>>;
-
-    fn next(&mut self) -> Option<Result<O, ParseIterError<E>>> {
-        if self.input.is_eof() {
-            return None;
+    /// Recovers from errors with `recover` instead of ending the iteration
+    #[inline(always)]
+    pub fn recover<R2: Recover + Clone>(self, recover: R2) -> ParseMutIter<'a, I, T, P, R2> {
+        ParseMutIter {
+            input: self.input,
+            trimmer: self.trimmer,
+            parser: self.parser,
+            recovery: self.recovery.with_recover(recover),
         }
+    }
 
-        let output = match self.parser.parse(self.input) {
-            Ok(output) => output,
-            Err(ParseError::ReadError(ReadError::EOF)) => return None,
-            Err(ParseError::ReadError(ReadError::InvalidUtf8(err))) => {
-                return Some(Err(ParseIterError::InvalidUtf8(err)));
-            }
-            Err(ParseError::Error(err)) => return Some(Err(ParseIterError::Error(err))),
-        };
+    /// Ends the iteration after `max_errors` errors in a row
+    #[inline(always)]
+    pub fn max_errors(mut self, max_errors: usize) -> Self {
+        self.recovery.max_errors = max_errors;
+        self
+    }
+}
 
-        if let Err(ReadError::InvalidUtf8(err)) = self.trimmer.clone().trim(self.input) {
-            return Some(Err(ParseIterError::InvalidUtf8(err)));
-        }
+impl<'a, I: ?Sized + Input, T: TryTrim<E> + Clone, P, O, E, R: Recover + Clone> Iterator
+    for ParseMutIter<'a, I, T, P, R>
+where
+    for<'s, 'k> &'s mut P: IsParse<'k, Output = O, Error = E>,
+{
+    type Item = Result<O, ParseIterError<E>>;
 
-        Some(Ok(output))
+    fn next(&mut self) -> Option<Result<O, ParseIterError<E>>> {
+        self.recovery
+            .next(self.input, &self.trimmer, |input| self.parser.parse(input))
     }
 }
@@ -0,0 +1,166 @@
+use crate::{
+    input::Input,
+    parse::{IsParse, Parse, ParseIterError, iter_result},
+    trim::{Trivia, TryTrim},
+};
+
+/// A `ParseIter` that yields each item with the trivia trimmed before it,
+/// such as to insert a semicolon where an item follows a newline
+///
+/// The iteration ends after the first error.
+#[derive(Debug)]
+pub struct ParseTriviaIter<'a, I: ?Sized + Input, T, P: Parse> {
+    input: &'a mut I,
+    trimmer: T,
+    parser: P,
+    /// The trivia trimmed since the last item
+    trivia: Option<Trivia>,
+    /// Whether an error ended the iteration
+    done: bool,
+}
+
+impl<'a, I: ?Sized + Input, T: TryTrim<E> + Clone, P: for<'s> IsParse<'s, Error = E> + Clone, E>
+    ParseTriviaIter<'a, I, T, P>
+{
+    #[inline(always)]
+    pub fn new(input: &'a mut I, trimmer: T, parser: P) -> Result<Self, ParseIterError<E>> {
+        let trivia = iter_result(trimmer.clone().trim_trivia(input))?.unwrap_or_default();
+
+        Ok(Self {
+            input,
+            trimmer,
+            parser,
+            trivia: Some(trivia),
+            done: false,
+        })
+    }
+}
+
+impl<
+    'a,
+    I: ?Sized + Input,
+    T: TryTrim<E> + Clone,
+    P: for<'s> IsParse<'s, Output = O, Error = E> + Clone,
+    O,
+    E,
+> Iterator for ParseTriviaIter<'a, I, T, P>
+{
+    type Item = Result<(Trivia, O), ParseIterError<E>>;
+
+    fn next(&mut self) -> Option<Result<(Trivia, O), ParseIterError<E>>> {
+        if self.done {
+            return None;
+        }
+
+        let trivia = match self.trivia {
+            Some(trivia) => trivia,
+            None => match iter_result(self.trimmer.clone().trim_trivia(self.input)) {
+                Ok(trivia) => {
+                    self.trivia = trivia;
+                    trivia?
+                }
+                Err(err) => {
+                    self.done = true;
+                    return Some(Err(err));
+                }
+            },
+        };
+
+        if self.input.is_eof() {
+            return None;
+        }
+
+        match iter_result(self.parser.clone().parse(self.input)) {
+            Ok(output) => {
+                self.trivia = None;
+                output.map(|output| Ok((trivia, output)))
+            }
+            Err(err) => {
+                self.done = true;
+                Some(Err(err))
+            }
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use token_precedence::span::Span;
+
+    use crate::{
+        core::{
+            parse::{ExpectedTag, Tag},
+            trim::{TrimComments, TrimWhitespace, UnterminatedComment},
+        },
+        input::StrView,
+        parse::ParseExt,
+    };
+
+    use super::*;
+
+    #[test]
+    fn newlines() {
+        let mut input = StrView::new("a a\na\n\n a");
+        let trivia: Vec<_> = ParseTriviaIter::new(&mut input, TrimWhitespace, Tag("a"))
+            .unwrap()
+            .map(|item| item.unwrap().0)
+            .collect();
+
+        assert_eq!(
+            trivia
+                .iter()
+                .map(|trivia| trivia.newlines)
+                .collect::<Vec<_>>(),
+            [0, 0, 1, 2]
+        );
+        assert_eq!(trivia[3].span, Span::new(5, 8));
+    }
+
+    #[test]
+    fn comments() {
+        const COMMENTS: TrimComments = TrimComments::new(&["//"], &[]);
+
+        #[derive(Debug, PartialEq)]
+        enum Error {
+            Tag,
+            Comment,
+        }
+
+        impl From<UnterminatedComment> for Error {
+            fn from(_: UnterminatedComment) -> Self {
+                Error::Comment
+            }
+        }
+
+        let mut input = StrView::new("a // b\n\na");
+        let trivia: Vec<_> =
+            ParseTriviaIter::new(&mut input, COMMENTS, Tag("a").map_err(|_| Error::Tag))
+                .unwrap()
+                .map(|item| item.unwrap().0)
+                .collect();
+
+        assert_eq!(trivia.len(), 2);
+        assert!(trivia[1].comment);
+        assert_eq!(trivia[1].newlines, 2);
+    }
+
+    #[test]
+    fn ends_after_error() {
+        let mut input = StrView::new("a\nb a");
+        let items: Vec<_> = ParseTriviaIter::new(&mut input, TrimWhitespace, Tag("a"))
+            .unwrap()
+            .map(|item| item.map(|(trivia, output)| (trivia.newlines, output)))
+            .collect();
+
+        assert_eq!(
+            items,
+            [
+                Ok((0, Span::new(0, 1))),
+                Err(ParseIterError::Error(ExpectedTag {
+                    tag: "a",
+                    span: Span::new(2, 2)
+                }))
+            ]
+        );
+    }
+}
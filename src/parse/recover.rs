@@ -0,0 +1,322 @@
+use crate::{
+    input::{self, Input, InputExt, ReadError},
+    parse::{Parse, ParseError, ParseIterError, iter_result},
+    trim::TryTrim,
+};
+
+/// Skips input after an item of an iterator fails to parse, so that the next item can be parsed
+///
+/// # Note
+/// An iterator ends after its `max_errors` limit of errors in a row,
+/// without recovering from the error that reaches it,
+/// so `max_errors(1)` ends at the first error like an iterator without recovery.
+/// If recovering leaves the input where the failed item started,
+/// the iterator skips a char so that the next item does not fail the same way.
+pub trait Recover {
+    /// Returns false if the input cannot be recovered
+    fn recover<I: ?Sized + Input>(self, input: &mut I) -> input::Result<bool>;
+}
+
+/// Does not recover, ending the iteration at the first error
+impl Recover for () {
+    #[inline(always)]
+    fn recover<I: ?Sized + Input>(self, _input: &mut I) -> input::Result<bool> {
+        Ok(false)
+    }
+}
+
+/// Skips past the next char of `.0`, such as a `;` or newline ending a record
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub struct SyncChars<'s>(pub &'s str);
+
+impl Recover for SyncChars<'_> {
+    #[inline(always)]
+    fn recover<I: ?Sized + Input>(self, input: &mut I) -> input::Result<bool> {
+        input.consume_until(8, |c| self.0.contains(c))?;
+        input.peek_entry()?.consume();
+
+        Ok(true)
+    }
+}
+
+/// Skips input until `.0` parses, consuming what it matched
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub struct SyncParse<P>(pub P);
+
+impl<P: Parse + Clone> Recover for SyncParse<P> {
+    fn recover<I: ?Sized + Input>(self, input: &mut I) -> input::Result<bool> {
+        loop {
+            let start = input.index();
+
+            // The output and error of the parser may borrow the input, so they are dropped first
+            let result = self
+                .0
+                .clone()
+                .parse(&mut *input)
+                .map(drop)
+                .map_err(|err| match err {
+                    ParseError::ReadError(err) => Some(err),
+                    ParseError::Error(_) => None,
+                });
+
+            match result {
+                Ok(()) => return Ok(true),
+                Err(Some(err)) => return Err(err),
+                Err(None) if input.index() == start => input.peek_entry()?.consume(),
+                Err(None) => {}
+            }
+        }
+    }
+}
+
+/// The trimming and error recovery state of a parse iterator
+#[derive(Debug)]
+pub(crate) struct Recovery<R> {
+    recover: R,
+    /// The number of errors in a row that ends the iteration
+    pub(crate) max_errors: usize,
+    errors: usize,
+    /// Whether the input has been trimmed since the last item
+    trimmed: bool,
+    done: bool,
+    /// A read error hit while recovering, yielded after the error that was recovered from
+    read_error: Option<ReadError>,
+}
+
+impl Recovery<()> {
+    /// Trims the input before the first item
+    #[inline(always)]
+    pub(crate) fn start<I: ?Sized + Input, T: TryTrim<E>, E>(
+        input: &mut I,
+        trimmer: T,
+    ) -> Result<Self, ParseIterError<E>> {
+        iter_result(trimmer.try_trim(input))?;
+
+        Ok(Self {
+            recover: (),
+            max_errors: usize::MAX,
+            errors: 0,
+            trimmed: true,
+            done: false,
+            read_error: None,
+        })
+    }
+}
+
+impl<R> Recovery<R> {
+    #[inline(always)]
+    pub(crate) fn with_recover<R2>(self, recover: R2) -> Recovery<R2> {
+        Recovery {
+            recover,
+            max_errors: self.max_errors,
+            errors: self.errors,
+            trimmed: self.trimmed,
+            done: self.done,
+            read_error: self.read_error,
+        }
+    }
+
+    /// Parses the next item of an iterator with `parse`, trimming the input before it
+    ///
+    /// An error ends the iteration unless the input is recovered,
+    /// skipping a char if the recovery would leave it where the item started.
+    pub(crate) fn next<I: ?Sized + Input, T: TryTrim<E> + Clone, O, E>(
+        &mut self,
+        input: &mut I,
+        trimmer: &T,
+        parse: impl FnOnce(&mut I) -> Result<O, ParseError<E>>,
+    ) -> Option<Result<O, ParseIterError<E>>>
+    where
+        R: Recover + Clone,
+    {
+        if self.done {
+            let err = self.read_error.take()?;
+            return iter_result::<O, E>(Err(err.into())).err().map(Err);
+        }
+
+        if !self.trimmed {
+            self.trimmed = true;
+
+            match iter_result(trimmer.clone().try_trim(input)) {
+                Ok(Some(())) => {}
+                Ok(None) => return None,
+                Err(err) => {
+                    self.done = true;
+                    return Some(Err(err));
+                }
+            }
+        }
+
+        if input.is_eof() {
+            return None;
+        }
+
+        // Trimmed lazily so that a trim error does not lose the output
+        self.trimmed = false;
+
+        let start = input.index();
+
+        match iter_result(parse(input)) {
+            Ok(Some(output)) => {
+                self.errors = 0;
+                Some(Ok(output))
+            }
+            Ok(None) => None,
+            Err(ParseIterError::Error(err)) => {
+                self.failed(input, start);
+                Some(Err(ParseIterError::Error(err)))
+            }
+            Err(err) => {
+                self.done = true;
+                Some(Err(err))
+            }
+        }
+    }
+
+    /// Recovers the input after an item starting at `start` failed, ending the iteration if it cannot
+    fn failed<I: ?Sized + Input>(&mut self, input: &mut I, start: usize)
+    where
+        R: Recover + Clone,
+    {
+        self.errors += 1;
+
+        if self.errors >= self.max_errors {
+            self.done = true;
+            return;
+        }
+
+        let result = self.recover.clone().recover(input).and_then(|recovered| {
+            // Parsing from the same input would fail the same way forever
+            if recovered && input.index() == start {
+                input.peek_entry()?.consume();
+            }
+
+            Ok(recovered)
+        });
+
+        match result {
+            // The end of input is left for the iterator to find
+            Ok(true) | Err(ReadError::EOF) => {}
+            Ok(false) => self.done = true,
+            Err(err) => {
+                self.read_error = Some(err);
+                self.done = true;
+            }
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use token_precedence::span::Span;
+
+    use crate::{
+        core::{
+            parse::{Integer, NumberError, NumberErrorKind, Tag},
+            trim::TrimWhitespace,
+        },
+        input::{InvalidUtf8, ReaderInput, StrView},
+        parse::{ParseExt, ParseIter, ParseIterError, ParseMutIter},
+    };
+
+    use super::*;
+
+    fn integers<R: Recover + Clone>(s: &str, recover: R, max_errors: usize) -> Vec<Option<u32>> {
+        let mut input = StrView::new(s);
+
+        ParseIter::new(&mut input, TrimWhitespace, Integer::<u32>::new())
+            .unwrap()
+            .recover(recover)
+            .max_errors(max_errors)
+            .map(|item| item.ok().map(|output| output.inner))
+            .collect()
+    }
+
+    #[test]
+    fn without_recovery() {
+        let mut input = StrView::new("1 x 2");
+        let items: Vec<_> = ParseIter::new(&mut input, TrimWhitespace, Integer::<u32>::new())
+            .unwrap()
+            .collect();
+
+        assert_eq!(
+            items,
+            [
+                Ok(Span::new(0, 1).over(1)),
+                Err(ParseIterError::Error(NumberError {
+                    kind: NumberErrorKind::Expected,
+                    span: Span::new(2, 2)
+                }))
+            ]
+        );
+    }
+
+    #[test]
+    fn sync_chars() {
+        assert_eq!(
+            integers("1 x, 2 0b2,, 3", SyncChars(","), usize::MAX),
+            [Some(1), None, Some(2), None, None, Some(3)]
+        );
+        assert_eq!(
+            integers("1 x, y, z, 2", SyncChars(","), 2),
+            [Some(1), None, None]
+        );
+        assert_eq!(integers("x 1", SyncChars(";"), usize::MAX), [None]);
+        assert_eq!(integers("1 x, 2", SyncChars(","), 1), [Some(1), None]);
+    }
+
+    #[test]
+    fn invalid_utf8_while_recovering() {
+        let mut input = ReaderInput::<_, 16>::new(&b"1 x                    \xff, 2"[..]);
+
+        let items: Vec<_> = ParseIter::new(&mut input, TrimWhitespace, Integer::<u32>::new())
+            .unwrap()
+            .recover(SyncChars(","))
+            .map(|item| item.map(|output| output.inner))
+            .collect();
+
+        assert_eq!(
+            items,
+            [
+                Ok(1),
+                Err(ParseIterError::Error(NumberError {
+                    kind: NumberErrorKind::Expected,
+                    span: Span::new(2, 2)
+                })),
+                Err(ParseIterError::InvalidUtf8(InvalidUtf8 { index: 23 }))
+            ]
+        );
+    }
+
+    #[test]
+    fn sync_parse() {
+        assert_eq!(
+            integers("1 x y; 2", SyncParse(Tag(";")), usize::MAX),
+            [Some(1), None, Some(2)]
+        );
+
+        let mut count = 0;
+        let parser = Integer::<u32>::new().mapped_mut(|_| count += 1);
+
+        let mut input = StrView::new("1 a 2 b 3");
+        let errors = ParseMutIter::new(&mut input, TrimWhitespace, parser)
+            .unwrap()
+            .recover(SyncParse(Tag(" ")))
+            .filter(Result::is_err)
+            .count();
+
+        assert_eq!((count, errors), (3, 2));
+    }
+
+    #[test]
+    fn recovery_without_progress() {
+        assert_eq!(
+            integers("1 x 2", SyncParse(Tag("").opt()), usize::MAX),
+            [Some(1), None, Some(2)]
+        );
+        assert_eq!(
+            integers("1 xy", SyncParse(Tag("").opt()), usize::MAX),
+            [Some(1), None, None]
+        );
+    }
+}
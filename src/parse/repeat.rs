@@ -0,0 +1,487 @@
+use std::{
+    marker::PhantomData,
+    ops::{Bound, RangeBounds},
+};
+
+use crate::{
+    input::{Input, ReadError},
+    parse::{IsParse, Parse, ParseError},
+    trim::TryTrim,
+};
+
+/// Parses `parse` zero or more times, collecting the outputs into `C`
+#[inline(always)]
+pub const fn many0<P, C>(parse: P) -> Repeat<P, C> {
+    Repeat::new(parse, 0, usize::MAX)
+}
+
+/// Parses `parse` one or more times, collecting the outputs into `C`
+#[inline(always)]
+pub const fn many1<P, C>(parse: P) -> Repeat<P, C> {
+    Repeat::new(parse, 1, usize::MAX)
+}
+
+/// Parses `parse` a number of times within `range`, collecting the outputs into `C`
+///
+/// # Panics
+/// If `range` is empty
+#[inline(always)]
+#[track_caller]
+pub fn repeat<P, C>(parse: P, range: impl RangeBounds<usize>) -> Repeat<P, C> {
+    let min = match range.start_bound() {
+        Bound::Included(&min) => min,
+        Bound::Excluded(&min) => min.saturating_add(1),
+        Bound::Unbounded => 0,
+    };
+    let max = match range.end_bound() {
+        Bound::Included(&max) => max,
+        Bound::Excluded(&end) => {
+            assert!(end > min, "repeat range is empty");
+            end - 1
+        }
+        Bound::Unbounded => usize::MAX,
+    };
+
+    Repeat::new(parse, min, max)
+}
+
+/// Parses zero or more `item`s separated by `separator`, collecting the items into `C`
+#[inline(always)]
+pub const fn separated_list<P, S, C>(item: P, separator: S) -> SeparatedList<P, S, C> {
+    SeparatedList {
+        item,
+        separator,
+        trim: (),
+        trailing: false,
+        _collection: PhantomData,
+    }
+}
+
+/// Parses `parse` zero or more times, folding each output into `init` with `fold`
+#[inline(always)]
+pub const fn fold_many<P, A, F>(parse: P, init: A, fold: F) -> FoldMany<P, A, F> {
+    FoldMany {
+        parse,
+        init,
+        fold,
+        trim: (),
+    }
+}
+
+/// # Note
+/// Repetition stops at the first item that fails without consuming input,
+/// an item that fails after consuming input fails the whole repetition.
+/// Repetition also stops after an item that succeeds without consuming input
+/// once `min` items were parsed, as it would otherwise never end.
+///
+/// Fewer than `min` items fail with the error of the last item,
+/// or with `ReadError::EOF` if the input ended first.
+#[derive(Debug, Clone, Copy)]
+pub struct Repeat<P, C, T = ()> {
+    pub parse: P,
+    pub min: usize,
+    pub max: usize,
+    pub trim: T,
+    _collection: PhantomData<fn() -> C>,
+}
+
+impl<P, C> Repeat<P, C> {
+    /// # Panics
+    /// If `min` is greater than `max`
+    #[inline(always)]
+    #[track_caller]
+    pub const fn new(parse: P, min: usize, max: usize) -> Self {
+        assert!(min <= max, "repeat min is greater than max");
+
+        Self {
+            parse,
+            min,
+            max,
+            trim: (),
+            _collection: PhantomData,
+        }
+    }
+}
+
+impl<P, C, T> Repeat<P, C, T> {
+    /// Trims with `trim` between items
+    #[inline(always)]
+    pub fn trimmed<T2>(self, trim: T2) -> Repeat<P, C, T2> {
+        Repeat {
+            parse: self.parse,
+            min: self.min,
+            max: self.max,
+            trim,
+            _collection: PhantomData,
+        }
+    }
+}
+
+impl<'a, P, C, T, O, E> IsParse<'a> for Repeat<P, C, T>
+where
+    P: for<'s> IsParse<'s, Output = O, Error = E> + Clone,
+    C: Default + Extend<O>,
+    T: TryTrim<E> + Clone,
+{
+    type Output = C;
+    type Error = E;
+
+    fn __parse<I: ?Sized + Input>(
+        self,
+        input: &'a mut I,
+    ) -> Result<Self::Output, ParseError<Self::Error>> {
+        let mut collection = C::default();
+
+        repeat_with(
+            &self.parse,
+            &self.trim,
+            self.min,
+            self.max,
+            input,
+            |output| collection.extend(Some(output)),
+        )?;
+
+        Ok(collection)
+    }
+}
+
+/// # Note
+/// An item must follow every separator unless `allow_trailing` is set,
+/// a separator that fails without consuming input ends the list.
+#[derive(Debug, Clone, Copy)]
+pub struct SeparatedList<P, S, C, T = ()> {
+    pub item: P,
+    pub separator: S,
+    pub trim: T,
+    pub trailing: bool,
+    _collection: PhantomData<fn() -> C>,
+}
+
+impl<P, S, C, T> SeparatedList<P, S, C, T> {
+    /// Trims with `trim` around separators
+    #[inline(always)]
+    pub fn trimmed<T2>(self, trim: T2) -> SeparatedList<P, S, C, T2> {
+        SeparatedList {
+            item: self.item,
+            separator: self.separator,
+            trim,
+            trailing: self.trailing,
+            _collection: PhantomData,
+        }
+    }
+
+    /// Accepts a separator after the last item
+    #[inline(always)]
+    pub fn allow_trailing(mut self) -> Self {
+        self.trailing = true;
+        self
+    }
+}
+
+impl<'a, P, S, C, T, O, E> IsParse<'a> for SeparatedList<P, S, C, T>
+where
+    P: for<'s> IsParse<'s, Output = O, Error = E> + Clone,
+    S: for<'s> IsParse<'s, Error = E> + Clone,
+    C: Default + Extend<O>,
+    T: TryTrim<E> + Clone,
+{
+    type Output = C;
+    type Error = E;
+
+    fn __parse<I: ?Sized + Input>(
+        self,
+        input: &'a mut I,
+    ) -> Result<Self::Output, ParseError<Self::Error>> {
+        let mut collection = C::default();
+
+        match attempt(self.item.clone(), input)? {
+            Some(output) => collection.extend(Some(output)),
+            None => return Ok(collection),
+        }
+
+        loop {
+            if trim(self.trim.clone(), input)?.is_none() {
+                break;
+            }
+
+            let start = input.index();
+
+            // The output of the separator may borrow the input, so it is dropped first
+            match self.separator.clone().parse(&mut *input).map(drop) {
+                Ok(()) => {}
+                Err(ParseError::Error(_)) if input.index() == start => break,
+                Err(ParseError::ReadError(ReadError::EOF))
+                    if input.is_eof() || input.index() == start =>
+                {
+                    break;
+                }
+                Err(err) => return Err(err),
+            }
+
+            if trim(self.trim.clone(), input)?.is_none() {
+                if self.trailing {
+                    break;
+                }
+
+                return Err(ParseError::ReadError(ReadError::EOF));
+            }
+
+            let start = input.index();
+
+            match self.item.clone().parse(&mut *input) {
+                Ok(output) => collection.extend(Some(output)),
+                Err(ParseError::Error(_)) if self.trailing && input.index() == start => break,
+                Err(ParseError::ReadError(ReadError::EOF))
+                    if self.trailing && (input.is_eof() || input.index() == start) =>
+                {
+                    break;
+                }
+                Err(err) => return Err(err),
+            }
+        }
+
+        Ok(collection)
+    }
+}
+
+#[derive(Debug, Clone, Copy)]
+pub struct FoldMany<P, A, F, T = ()> {
+    pub parse: P,
+    pub init: A,
+    pub fold: F,
+    pub trim: T,
+}
+
+impl<P, A, F, T> FoldMany<P, A, F, T> {
+    /// Trims with `trim` between items
+    #[inline(always)]
+    pub fn trimmed<T2>(self, trim: T2) -> FoldMany<P, A, F, T2> {
+        FoldMany {
+            parse: self.parse,
+            init: self.init,
+            fold: self.fold,
+            trim,
+        }
+    }
+}
+
+impl<'a, P, A, F, T, O, E> IsParse<'a> for FoldMany<P, A, F, T>
+where
+    P: for<'s> IsParse<'s, Output = O, Error = E> + Clone,
+    F: FnMut(A, O) -> A,
+    T: TryTrim<E> + Clone,
+{
+    type Output = A;
+    type Error = E;
+
+    fn __parse<I: ?Sized + Input>(
+        mut self,
+        input: &'a mut I,
+    ) -> Result<Self::Output, ParseError<Self::Error>> {
+        let mut acc = Some(self.init);
+
+        repeat_with(&self.parse, &self.trim, 0, usize::MAX, input, |output| {
+            acc = acc.take().map(|acc| (self.fold)(acc, output));
+        })?;
+
+        Ok(acc.expect("fold accumulator is always restored"))
+    }
+}
+
+fn repeat_with<P, T, O, E, I: ?Sized + Input>(
+    parse: &P,
+    trimmer: &T,
+    min: usize,
+    max: usize,
+    input: &mut I,
+    mut push: impl FnMut(O),
+) -> Result<(), ParseError<E>>
+where
+    P: for<'s> IsParse<'s, Output = O, Error = E> + Clone,
+    T: TryTrim<E> + Clone,
+{
+    let mut count = 0;
+
+    while count < max {
+        if count > 0 && trim(trimmer.clone(), input)?.is_none() {
+            break;
+        }
+
+        let start = input.index();
+
+        match parse.clone().parse(&mut *input) {
+            Ok(output) => {
+                push(output);
+                count += 1;
+
+                if input.index() == start && count >= min {
+                    break;
+                }
+            }
+            Err(ParseError::Error(error)) if input.index() != start || count < min => {
+                return Err(ParseError::Error(error));
+            }
+            Err(ParseError::Error(_)) => break,
+            Err(ParseError::ReadError(ReadError::EOF))
+                if count >= min && (input.is_eof() || input.index() == start) =>
+            {
+                break;
+            }
+            Err(ParseError::ReadError(err)) => return Err(ParseError::ReadError(err)),
+        }
+    }
+
+    // Trimming reached the end of input before enough items were parsed
+    if count < min {
+        return Err(ParseError::ReadError(ReadError::EOF));
+    }
+
+    Ok(())
+}
+
+/// Runs `parse`, returning `None` if it fails without consuming input or reaches the end of input
+#[inline(always)]
+fn attempt<P: for<'s> IsParse<'s, Output = O, Error = E>, O, E, I: ?Sized + Input>(
+    parse: P,
+    input: &mut I,
+) -> Result<Option<O>, ParseError<E>> {
+    let start = input.index();
+
+    match parse.parse(&mut *input) {
+        Ok(output) => Ok(Some(output)),
+        Err(ParseError::Error(_)) if input.index() == start => Ok(None),
+        Err(ParseError::ReadError(ReadError::EOF)) if input.is_eof() || input.index() == start => {
+            Ok(None)
+        }
+        Err(err) => Err(err),
+    }
+}
+
+/// Trims the input, returning `None` if the end of input was reached
+#[inline(always)]
+fn trim<T: TryTrim<E>, E, I: ?Sized + Input>(
+    trim: T,
+    input: &mut I,
+) -> Result<Option<()>, ParseError<E>> {
+    match trim.try_trim(input) {
+        Ok(()) => Ok(Some(())),
+        Err(ParseError::ReadError(ReadError::EOF)) => Ok(None),
+        Err(err) => Err(err),
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use std::collections::BTreeSet;
+
+    use crate::{
+        core::trim::TrimWhitespace,
+        input::StrView,
+        parse::testing::{COMMA, DIGITS, LETTERS},
+    };
+
+    use super::*;
+
+    #[test]
+    fn many() {
+        let mut input = StrView::new("ab1");
+
+        assert_eq!(many0(DIGITS).parse(&mut input), Ok(Vec::<String>::new()));
+        assert_eq!(
+            many1::<_, Vec<String>>(DIGITS).parse(&mut input),
+            Err(ParseError::Error("digits"))
+        );
+        assert_eq!(many1(LETTERS).parse(&mut input), Ok(vec!["ab".to_string()]));
+    }
+
+    #[test]
+    fn trimmed_into_set() {
+        let mut input = StrView::new("b a b 12");
+
+        let letters: Result<BTreeSet<String>, _> =
+            many0(LETTERS).trimmed(TrimWhitespace).parse(&mut input);
+
+        assert_eq!(
+            letters,
+            Ok(BTreeSet::from(["a".to_string(), "b".to_string()]))
+        );
+        assert_eq!(DIGITS.parse(&mut input), Ok("12".to_string()));
+    }
+
+    #[test]
+    fn repeat_bounds() {
+        let mut input = StrView::new("a b c d");
+
+        let letters: Result<Vec<String>, _> = repeat(LETTERS, 1..=3)
+            .trimmed(TrimWhitespace)
+            .parse(&mut input);
+        assert_eq!(letters.map(|letters| letters.len()), Ok(3));
+
+        let mut input = StrView::new("a 1");
+
+        let letters: Result<Vec<String>, _> = repeat(LETTERS, 2..)
+            .trimmed(TrimWhitespace)
+            .parse(&mut input);
+        assert_eq!(letters, Err(ParseError::Error("letters")));
+
+        let mut input = StrView::new("a b");
+
+        let letters: Result<Vec<String>, _> = repeat(LETTERS, 3..)
+            .trimmed(TrimWhitespace)
+            .parse(&mut input);
+        assert_eq!(letters, Err(ParseError::ReadError(ReadError::EOF)));
+
+        let mut input = StrView::new("1");
+
+        let empty: Result<Vec<Vec<String>>, _> = repeat(many0(LETTERS), 2..).parse(&mut input);
+        assert_eq!(empty.map(|empty| empty.len()), Ok(2));
+    }
+
+    #[test]
+    #[should_panic(expected = "repeat min is greater than max")]
+    fn repeat_empty_range() {
+        Repeat::<_, Vec<String>>::new(LETTERS, 3, 2);
+    }
+
+    #[test]
+    #[should_panic(expected = "repeat range is empty")]
+    fn repeat_empty_excluded_range() {
+        repeat::<_, Vec<String>>(LETTERS, 0..0);
+    }
+
+    #[test]
+    fn separated() {
+        let mut input = StrView::new("1, 2 ,3;");
+
+        let list: Result<Vec<String>, _> = separated_list(DIGITS, COMMA)
+            .trimmed(TrimWhitespace)
+            .parse(&mut input);
+        assert_eq!(
+            list,
+            Ok(vec!["1".to_string(), "2".to_string(), "3".to_string()])
+        );
+
+        let mut input = StrView::new("1,;");
+        let list: Result<Vec<String>, _> = separated_list(DIGITS, COMMA).parse(&mut input);
+        assert_eq!(list, Err(ParseError::Error("digits")));
+
+        let mut input = StrView::new("1,;");
+        let list: Result<Vec<String>, _> = separated_list(DIGITS, COMMA)
+            .allow_trailing()
+            .parse(&mut input);
+        assert_eq!(list, Ok(vec!["1".to_string()]));
+    }
+
+    #[test]
+    fn fold() {
+        let mut input = StrView::new("1 20 300");
+
+        let sum = fold_many(DIGITS, 0, |sum, digits: String| {
+            sum + digits.parse::<u32>().unwrap()
+        })
+        .trimmed(TrimWhitespace)
+        .parse(&mut input);
+
+        assert_eq!(sum, Ok(321));
+    }
+}
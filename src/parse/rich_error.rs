@@ -0,0 +1,262 @@
+use std::{borrow::Cow, cmp, fmt};
+
+use token_precedence::span::{Span, Spanned};
+
+use crate::{
+    input::Input,
+    parse::{AltError, IsParse, Parse, ParseError, ParseErrorOutput, ParseOutput, Unexpected},
+};
+
+/// An error for any grammar, with what was expected at `span` and what it happened within
+///
+/// # Note
+/// The errors of this crate convert into a `RichError`, `Unexpected` only when its output
+/// is a `Span` or a `Spanned` value that can be displayed,
+/// so `ParseExt::context` can be used on the parsers of this crate.
+#[derive(Debug, Clone, PartialEq, Eq, Hash)]
+pub struct RichError {
+    pub span: Span,
+    /// What was expected at `span` without duplicates, such as `"identifier"` or `"`let`"`
+    pub expected: Vec<Cow<'static, str>>,
+    /// A snippet of what was found instead
+    pub found: Option<String>,
+    /// What the parser was within, innermost first
+    pub context: Vec<&'static str>,
+}
+
+impl RichError {
+    #[inline(always)]
+    pub const fn new(span: Span) -> Self {
+        Self {
+            span,
+            expected: Vec::new(),
+            found: None,
+            context: Vec::new(),
+        }
+    }
+
+    #[inline(always)]
+    pub fn expecting(mut self, expected: impl Into<Cow<'static, str>>) -> Self {
+        let expected = expected.into();
+
+        if !self.expected.contains(&expected) {
+            self.expected.push(expected);
+        }
+
+        self
+    }
+
+    #[inline(always)]
+    pub fn found(mut self, found: impl Into<String>) -> Self {
+        self.found = Some(found.into());
+        self
+    }
+
+    /// Merges the errors of two alternatives
+    ///
+    /// # Note
+    /// Only errors at the same span are combined, keeping the context they share.
+    /// Otherwise the error that got furthest is kept whole, as an alternative that
+    /// recognized more of the input is more likely to be the one that was meant.
+    pub fn merge(mut self, other: RichError) -> Self {
+        if self.span != other.span {
+            return cmp::max_by(self, other, |a, b| a.span.cmp(&b.span));
+        }
+
+        self.found = self.found.or(other.found);
+
+        // Contexts are innermost first, so the shared contexts are at the end
+        let shared = self
+            .context
+            .iter()
+            .rev()
+            .zip(other.context.iter().rev())
+            .take_while(|(a, b)| a == b)
+            .count();
+        self.context.drain(..self.context.len() - shared);
+
+        for expected in other.expected {
+            self = self.expecting(expected);
+        }
+
+        self
+    }
+}
+
+impl From<!> for RichError {
+    #[inline(always)]
+    fn from(value: !) -> Self {
+        value
+    }
+}
+
+impl From<Unexpected<Span>> for RichError {
+    #[inline(always)]
+    fn from(value: Unexpected<Span>) -> Self {
+        RichError::new(value.found)
+    }
+}
+
+impl<T: fmt::Display> From<Unexpected<Spanned<T>>> for RichError {
+    #[inline(always)]
+    fn from(value: Unexpected<Spanned<T>>) -> Self {
+        RichError::new(value.found.span).found(format!("`{}`", value.found.inner))
+    }
+}
+
+impl<E: Into<RichError>> From<AltError<E>> for RichError {
+    fn from(value: AltError<E>) -> Self {
+        match value {
+            AltError::Expected(errors) => errors
+                .into_iter()
+                .map(Into::into)
+                .reduce(RichError::merge)
+                .expect("an alt error has an error for each branch"),
+            AltError::Committed { error, .. } => error.into(),
+        }
+    }
+}
+
+impl fmt::Display for RichError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match &self.expected[..] {
+            [] => write!(f, "unexpected input")?,
+            [expected] => write!(f, "expected {expected}")?,
+            [expected @ .., last] => write!(f, "expected one of {}, {last}", expected.join(", "))?,
+        }
+
+        if let Some(found) = &self.found {
+            write!(f, ", found {found}")?;
+        }
+
+        for context in &self.context {
+            write!(f, ", in {context}")?;
+        }
+
+        Ok(())
+    }
+}
+
+impl std::error::Error for RichError {}
+
+#[derive(Debug, Clone, Copy)]
+pub struct ContextParse<P: Parse> {
+    pub parse: P,
+    pub context: &'static str,
+}
+
+impl<'a, P: Parse> IsParse<'a> for ContextParse<P>
+where
+    ParseErrorOutput<'a, P>: Into<RichError>,
+{
+    type Output = ParseOutput<'a, P>;
+    type Error = RichError;
+
+    #[inline(always)]
+    fn __parse<I: ?Sized + Input>(
+        self,
+        input: &'a mut I,
+    ) -> Result<Self::Output, ParseError<Self::Error>> {
+        self.parse.parse(input).map_err(|err| match err {
+            ParseError::ReadError(err) => ParseError::ReadError(err),
+            ParseError::Error(err) => {
+                let mut err = err.into();
+                err.context.push(self.context);
+
+                ParseError::Error(err)
+            }
+        })
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::{
+        core::parse::{Integer, OneOf, Tag},
+        input::StrView,
+        parse::{ParseExt, alt, preceded},
+    };
+
+    use super::*;
+
+    fn parse<P>(parse: P, s: &str) -> RichError
+    where
+        for<'a> P: IsParse<'a, Error = RichError>,
+    {
+        match parse.parse(&mut StrView::new(s)) {
+            Err(ParseError::Error(err)) => err,
+            _ => panic!("expected an error"),
+        }
+    }
+
+    #[test]
+    fn merges_alternatives() {
+        let keyword = alt((Tag("let"), Tag("fn"), Tag("let"))).context("statement");
+        let err = parse(keyword, "x");
+
+        assert_eq!(err.span, Span::new(0, 0));
+        assert_eq!(err.expected, ["`let`", "`fn`"]);
+        assert_eq!(err.context, ["statement"]);
+        assert_eq!(err.to_string(), "expected one of `let`, `fn`, in statement");
+
+        let number = alt((
+            Tag("-").context("sign"),
+            Integer::<u8>::new()
+                .mapped(|number: Spanned<u8>| number.span)
+                .context("number"),
+        ));
+        let err = parse(number.context("item"), "x");
+
+        assert_eq!(err.expected, ["`-`", "number"]);
+        assert_eq!(err.context, ["item"]);
+
+        let err = parse(number.context("item"), "256");
+
+        assert_eq!(err.span, Span::new(0, 3));
+        assert_eq!(err.expected, ["number in range"]);
+        assert_eq!(err.context, ["number", "item"]);
+
+        let sign = alt((
+            OneOf("+-")
+                .mapped(|sign: Spanned<char>| sign.span)
+                .context("sign"),
+            Tag("~").context("tilde"),
+        ));
+        let err = parse(sign.context("prefix").context("item"), "x");
+
+        assert_eq!(err.span, Span::new(0, 0));
+        assert_eq!(err.expected, ["`+`", "`-`", "`~`"]);
+        assert_eq!(err.found.as_deref(), Some("`x`"));
+        assert_eq!(err.context, ["prefix", "item"]);
+    }
+
+    #[test]
+    fn from_unexpected() {
+        let not_keyword = Tag("let")
+            .mapped(|span: Span| span.over("let"))
+            .not()
+            .context("name");
+        let err = parse(not_keyword, "let");
+
+        assert_eq!(err.span, Span::new(0, 3));
+        assert_eq!(err.to_string(), "unexpected input, found `let`, in name");
+    }
+
+    #[test]
+    fn found_and_context() {
+        let list = preceded(
+            Tag("[").map_err(RichError::from),
+            OneOf("abc").context("list item"),
+        )
+        .context("list");
+        let err = parse(list, "[x]");
+
+        assert_eq!(err.span, Span::new(1, 1));
+        assert_eq!(err.found.as_deref(), Some("`x`"));
+        assert_eq!(err.context, ["list item", "list"]);
+        assert_eq!(
+            err.to_string(),
+            "expected one of `a`, `b`, `c`, found `x`, in list item, in list"
+        );
+    }
+}
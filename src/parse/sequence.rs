@@ -0,0 +1,231 @@
+use crate::{
+    input::Input,
+    parse::{IsParse, Parse, ParseError},
+};
+
+// Every parser but the last is run on a reborrow of the input, so their outputs cannot borrow
+// from it. This is expressed by requiring `for<'s> IsParse<'s, Output = O>` with a single `O`.
+macro_rules! impl_tuple {
+    ($($parse:ident $output:ident $var:ident),+) => {
+        impl<'a, E, $($parse, $output),+> IsParse<'a> for ($($parse,)+)
+        where
+            $($parse: for<'s> IsParse<'s, Output = $output, Error = E>,)+
+        {
+            type Output = ($($output,)+);
+            type Error = E;
+
+            #[inline(always)]
+            fn __parse<I: ?Sized + Input>(
+                self,
+                input: &'a mut I,
+            ) -> Result<Self::Output, ParseError<Self::Error>> {
+                let ($($var,)+) = self;
+
+                Ok(($($var.parse(&mut *input)?,)+))
+            }
+        }
+    };
+}
+
+impl_tuple!(A OA a, B OB b);
+impl_tuple!(A OA a, B OB b, C OC c);
+impl_tuple!(A OA a, B OB b, C OC c, D OD d);
+impl_tuple!(A OA a, B OB b, C OC c, D OD d, E2 OE e);
+impl_tuple!(A OA a, B OB b, C OC c, D OD d, E2 OE e, F OF f);
+impl_tuple!(A OA a, B OB b, C OC c, D OD d, E2 OE e, F OF f, G OG g);
+impl_tuple!(A OA a, B OB b, C OC c, D OD d, E2 OE e, F OF f, G OG g, H OH h);
+impl_tuple!(A OA a, B OB b, C OC c, D OD d, E2 OE e, F OF f, G OG g, H OH h, I2 OI i);
+impl_tuple!(A OA a, B OB b, C OC c, D OD d, E2 OE e, F OF f, G OG g, H OH h, I2 OI i, J OJ j);
+impl_tuple!(A OA a, B OB b, C OC c, D OD d, E2 OE e, F OF f, G OG g, H OH h, I2 OI i, J OJ j, K OK k);
+impl_tuple!(A OA a, B OB b, C OC c, D OD d, E2 OE e, F OF f, G OG g, H OH h, I2 OI i, J OJ j, K OK k, L OL l);
+
+/// Runs `prefix` then `parse`, keeping only the output of `parse`
+#[inline(always)]
+pub const fn preceded<A, B>(prefix: A, parse: B) -> Preceded<A, B> {
+    Preceded { prefix, parse }
+}
+
+/// Runs `parse` then `suffix`, keeping only the output of `parse`
+#[inline(always)]
+pub const fn terminated<A, B>(parse: A, suffix: B) -> Terminated<A, B> {
+    Terminated { parse, suffix }
+}
+
+/// Runs `open`, `parse` and then `close`, keeping only the output of `parse`
+#[inline(always)]
+pub const fn delimited<L, P, R>(open: L, parse: P, close: R) -> Delimited<L, P, R> {
+    Delimited { open, parse, close }
+}
+
+#[derive(Debug, Clone, Copy)]
+pub struct Preceded<A, B> {
+    pub prefix: A,
+    pub parse: B,
+}
+
+impl<'a, A, B, E> IsParse<'a> for Preceded<A, B>
+where
+    A: for<'s> IsParse<'s, Error = E>,
+    B: IsParse<'a, Error = E>,
+{
+    // `parse` runs last, so its output may borrow from the input
+    type Output = B::Output;
+    type Error = E;
+
+    #[inline(always)]
+    fn __parse<I: ?Sized + Input>(
+        self,
+        input: &'a mut I,
+    ) -> Result<Self::Output, ParseError<Self::Error>> {
+        self.prefix.parse(&mut *input)?;
+        self.parse.__parse(input)
+    }
+}
+
+#[derive(Debug, Clone, Copy)]
+pub struct Terminated<A, B> {
+    pub parse: A,
+    pub suffix: B,
+}
+
+impl<'a, A, B, O, E> IsParse<'a> for Terminated<A, B>
+where
+    A: for<'s> IsParse<'s, Output = O, Error = E>,
+    B: for<'s> IsParse<'s, Error = E>,
+{
+    type Output = O;
+    type Error = E;
+
+    #[inline(always)]
+    fn __parse<I: ?Sized + Input>(
+        self,
+        input: &'a mut I,
+    ) -> Result<Self::Output, ParseError<Self::Error>> {
+        let output = self.parse.parse(&mut *input)?;
+        self.suffix.parse(input)?;
+
+        Ok(output)
+    }
+}
+
+#[derive(Debug, Clone, Copy)]
+pub struct Delimited<L, P, R> {
+    pub open: L,
+    pub parse: P,
+    pub close: R,
+}
+
+impl<'a, L, P, R, O, E> IsParse<'a> for Delimited<L, P, R>
+where
+    L: for<'s> IsParse<'s, Error = E>,
+    P: for<'s> IsParse<'s, Output = O, Error = E>,
+    R: for<'s> IsParse<'s, Error = E>,
+{
+    type Output = O;
+    type Error = E;
+
+    #[inline(always)]
+    fn __parse<I: ?Sized + Input>(
+        self,
+        input: &'a mut I,
+    ) -> Result<Self::Output, ParseError<Self::Error>> {
+        self.open.parse(&mut *input)?;
+        let output = self.parse.parse(&mut *input)?;
+        self.close.parse(input)?;
+
+        Ok(output)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::{
+        core::parse::SplitUpTo,
+        input::{Entry, StrView},
+        parse::{IsParse, Parse, ParseError, ParseExt},
+    };
+
+    use super::*;
+
+    fn word() -> impl for<'s> IsParse<'s, Output = String, Error = !> + Clone {
+        SplitUpTo::new(|c| !c.is_alphanumeric()).mapped(|entry: Entry| {
+            let ret = entry.get().to_string();
+            entry.consume();
+            ret
+        })
+    }
+
+    fn symbols() -> impl for<'s> IsParse<'s, Output = String, Error = !> + Clone {
+        SplitUpTo::new(char::is_alphanumeric).mapped(|entry: Entry| {
+            let ret = entry.get().to_string();
+            entry.consume();
+            ret
+        })
+    }
+
+    #[test]
+    fn tuple_sequence() {
+        let mut input = StrView::new("key=value;");
+
+        let output = (word(), symbols(), word(), symbols()).parse(&mut input);
+
+        assert_eq!(
+            output.map_err(ParseError::unwrap_infallible),
+            Ok((
+                "key".to_string(),
+                "=".to_string(),
+                "value".to_string(),
+                ";".to_string()
+            ))
+        );
+    }
+
+    #[test]
+    fn surrounded() {
+        let mut input = StrView::new("(inner)rest");
+
+        assert_eq!(
+            delimited(symbols(), word(), symbols())
+                .parse(&mut input)
+                .map_err(ParseError::unwrap_infallible),
+            Ok("inner".to_string())
+        );
+
+        let mut input = StrView::new("--flag=");
+
+        assert_eq!(
+            terminated(preceded(symbols(), word()), symbols())
+                .parse(&mut input)
+                .map_err(ParseError::unwrap_infallible),
+            Ok("flag".to_string())
+        );
+    }
+
+    #[test]
+    fn preceded_borrowed_output() {
+        let mut input = StrView::new("  word rest");
+
+        let entry = preceded(symbols(), SplitUpTo::new(char::is_whitespace))
+            .parse(&mut input)
+            .map_err(ParseError::unwrap_infallible)
+            .unwrap();
+
+        assert_eq!(entry.get(), "word");
+    }
+
+    #[test]
+    fn shared_error() {
+        #[derive(Debug, PartialEq)]
+        struct Never;
+
+        let mut input = StrView::new("a b");
+
+        let output = (
+            word().map_err(|err| -> Never { err }),
+            symbols().map_err(|err| -> Never { err }),
+        )
+            .parse(&mut input);
+
+        assert_eq!(output, Ok(("a".to_string(), " ".to_string())));
+    }
+}
@@ -0,0 +1,33 @@
+use crate::{
+    input::{Input, InputExt},
+    parse::{IsParse, ParseError},
+};
+
+/// Reads a non-empty run of chars matching `.0`, failing with `.1` without consuming anything
+#[derive(Clone, Copy)]
+pub(crate) struct Run(pub fn(char) -> bool, pub &'static str);
+
+impl<'a> IsParse<'a> for Run {
+    type Output = String;
+    type Error = &'static str;
+
+    fn __parse<I: ?Sized + Input>(
+        self,
+        input: &'a mut I,
+    ) -> Result<Self::Output, ParseError<Self::Error>> {
+        let entry = input.read_until_entry(8, |c| !(self.0)(c))?;
+
+        if entry.get().is_empty() {
+            return Err(ParseError::Error(self.1));
+        }
+
+        let ret = entry.get().to_string();
+        entry.consume();
+        Ok(ret)
+    }
+}
+
+pub(crate) const DIGITS: Run = Run(|c| c.is_ascii_digit(), "digits");
+pub(crate) const LETTERS: Run = Run(|c| c.is_ascii_alphabetic(), "letters");
+pub(crate) const SPACES: Run = Run(|c| c == ' ', "spaces");
+pub(crate) const COMMA: Run = Run(|c| c == ',', "comma");
@@ -1,11 +1,214 @@
-use crate::input::{self, Input};
+use token_precedence::span::Span;
 
+use crate::{
+    input::{self, Input, ReadError},
+    parse::ParseError,
+};
+
+/// Skips input between parsed items
 pub trait Trim {
     fn trim<I: ?Sized + Input>(self, input: &mut I) -> input::Result<()>;
 }
 
+/// Skips input between parsed items, failing with the error `E` of the parser being trimmed around
+///
+/// # Note
+/// Every `Trim` is a `TryTrim` for any `E`, the parse iterators and combinators take a `TryTrim`.
+pub trait TryTrim<E> {
+    fn try_trim<I: ?Sized + Input>(self, input: &mut I) -> Result<(), ParseError<E>>;
+
+    /// Trims the input, reporting what was skipped
+    #[inline(always)]
+    fn trim_trivia<I: ?Sized + Input>(self, input: &mut I) -> Result<Trivia, ParseError<E>>
+    where
+        Self: Sized,
+    {
+        let start = input.index();
+
+        let mut counting = Counting {
+            input,
+            newlines: 0,
+            comment: false,
+        };
+        self.try_trim(&mut counting)?;
+
+        Ok(Trivia {
+            newlines: counting.newlines,
+            comment: counting.comment,
+            span: Span::new(start, counting.input.index()),
+        })
+    }
+}
+
+/// What a trimmer skipped before an item
+#[derive(Default, Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub struct Trivia {
+    pub newlines: usize,
+    /// Whether anything other than whitespace was skipped, such as a comment
+    pub comment: bool,
+    pub span: Span,
+}
+
+/// Counts the trivia consumed from `input`
+struct Counting<'i, I: ?Sized + Input> {
+    input: &'i mut I,
+    newlines: usize,
+    comment: bool,
+}
+
+impl<'i, I: ?Sized + Input> Input for Counting<'i, I> {
+    #[inline(always)]
+    unsafe fn get_unchecked(&self, n: usize) -> &[u8] {
+        unsafe { self.input.get_unchecked(n) }
+    }
+
+    #[inline(always)]
+    fn index(&self) -> usize {
+        self.input.index()
+    }
+
+    #[inline(always)]
+    fn read(&self) -> &str {
+        self.input.read()
+    }
+
+    #[inline(always)]
+    fn buffer_at_least(&mut self, n: usize) -> input::Result<()> {
+        self.input.buffer_at_least(n)
+    }
+
+    #[inline(always)]
+    fn set_eof(&mut self) {
+        self.input.set_eof();
+    }
+
+    #[inline(always)]
+    fn is_eof(&self) -> bool {
+        self.input.is_eof()
+    }
+
+    #[inline(always)]
+    unsafe fn consume(&mut self, n: usize) {
+        for c in unsafe { self.input.read().get_unchecked(..n) }.chars() {
+            if c == '\n' {
+                self.newlines += 1;
+            } else if !c.is_whitespace() {
+                self.comment = true;
+            }
+        }
+
+        unsafe { self.input.consume(n) }
+    }
+
+    #[inline(always)]
+    fn trait_obj(&mut self) -> &mut dyn Input {
+        self
+    }
+}
+
+impl<T: Trim, E> TryTrim<E> for T {
+    #[inline(always)]
+    fn try_trim<I: ?Sized + Input>(self, input: &mut I) -> Result<(), ParseError<E>> {
+        self.trim(input).map_err(ParseError::ReadError)
+    }
+}
+
 impl Trim for () {
+    #[inline(always)]
     fn trim<I: ?Sized + Input>(self, _input: &mut I) -> input::Result<()> {
         Ok(())
     }
 }
+
+// Trimmers in a tuple are applied in sequence
+macro_rules! impl_tuple {
+    ($($trim:ident $var:ident),+) => {
+        impl<E, $($trim: TryTrim<E>),+> TryTrim<E> for ($($trim,)+) {
+            #[inline(always)]
+            fn try_trim<I: ?Sized + Input>(self, input: &mut I) -> Result<(), ParseError<E>> {
+                let ($($var,)+) = self;
+
+                $($var.try_trim(input)?;)+
+
+                Ok(())
+            }
+        }
+    };
+}
+
+impl_tuple!(A a);
+impl_tuple!(A a, B b);
+impl_tuple!(A a, B b, C c);
+impl_tuple!(A a, B b, C c, D d);
+impl_tuple!(A a, B b, C c, D d, E2 e);
+impl_tuple!(A a, B b, C c, D d, E2 e, F f);
+impl_tuple!(A a, B b, C c, D d, E2 e, F f, G g);
+impl_tuple!(A a, B b, C c, D d, E2 e, F f, G g, H h);
+
+/// Applies `.0` until it does not consume anything
+///
+/// # Note
+/// Usually wraps a tuple, so that trimmers can be applied in any order,
+/// such as `TrimRepeat((TrimWhitespace, line_comments))`.
+#[derive(Default, Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub struct TrimRepeat<T>(pub T);
+
+impl<E, T: TryTrim<E> + Clone> TryTrim<E> for TrimRepeat<T> {
+    fn try_trim<I: ?Sized + Input>(self, input: &mut I) -> Result<(), ParseError<E>> {
+        loop {
+            if input.is_eof() {
+                return Err(ParseError::ReadError(ReadError::EOF));
+            }
+
+            let start = input.index();
+
+            self.0.clone().try_trim(input)?;
+
+            if input.index() == start {
+                return Ok(());
+            }
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::{
+        core::trim::{TrimUntil, TrimWhitespace},
+        input::StrView,
+    };
+
+    use super::*;
+
+    /// Trims `s`, returning the index reached
+    fn trim<T: TryTrim<!>>(trim: T, s: &str) -> Result<usize, ParseError<!>> {
+        let mut input = StrView::new(s);
+        trim.try_trim(&mut input)?;
+
+        Ok(input.index())
+    }
+
+    #[test]
+    fn sequence() {
+        let dashes = TrimUntil::new(|c| c != '-');
+
+        assert_eq!(trim((TrimWhitespace, dashes), "  --x"), Ok(4));
+        assert_eq!(trim((TrimWhitespace, dashes), "-- x"), Ok(2));
+        assert_eq!(
+            trim((TrimWhitespace, dashes), "  --"),
+            Err(ParseError::ReadError(ReadError::EOF))
+        );
+    }
+
+    #[test]
+    fn repeat() {
+        let dashes = TrimUntil::new(|c| c != '-');
+
+        assert_eq!(trim(TrimRepeat((TrimWhitespace, dashes)), "- - -x"), Ok(5));
+        assert_eq!(trim(TrimRepeat((dashes, TrimWhitespace)), "x"), Ok(0));
+        assert_eq!(
+            trim(TrimRepeat((TrimWhitespace, dashes)), " - "),
+            Err(ParseError::ReadError(ReadError::EOF))
+        );
+    }
+}
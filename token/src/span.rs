@@ -10,6 +10,16 @@ impl Span {
         Self { start, end }
     }
 
+    #[inline(always)]
+    pub const fn start(self) -> usize {
+        self.start
+    }
+
+    #[inline(always)]
+    pub const fn end(self) -> usize {
+        self.end
+    }
+
     #[inline(always)]
     pub const fn from_self_to_other(self, other: Self) -> Self {
         Self {
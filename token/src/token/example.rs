@@ -64,9 +64,10 @@ enum Orderings {
 // TREES
 
 #[allow(unused)]
-#[derive(Debug, Eq, Hash, Ord, PartialEq, PartialOrd)]
+#[derive(Default, Debug, Eq, Hash, Ord, PartialEq, PartialOrd)]
 enum TokenTree {
     // Expr(ExprTree),
+    #[default]
     Semicolon,
 }
 
@@ -162,12 +163,6 @@ impl FromStackEntry for TokenTree {
 
 // HAS STATE TRANSITION
 
-impl Default for TokenTree {
-    fn default() -> Self {
-        Self::Semicolon
-    }
-}
-
 impl HasStateTransition<RawToken> for TokenTree {
     type Token = Token;
     type Ordering = Orderings;